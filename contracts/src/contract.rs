@@ -1,27 +1,128 @@
 //! Core contract implementation for the XLM Price Prediction Market.
 
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map, Vec};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal,
+    Map, Symbol, TryFromVal, Val, Vec,
+};
 
 use crate::errors::ContractError;
+use crate::oracle::PriceOracleClient;
 use crate::types::{
-    BetSide, DataKey, PrecisionPrediction, Round, RoundMode, UserPosition, UserStats,
+    BetSide, DataKey, DrawPolicy, ExtDataKey, HistoryEntry, PendingResolution, Portfolio,
+    PrecisionPrediction, ResolvedRound, Round, RoundMode, RoundSummary, RoundingMode, TieBreakMode,
+    UserPosition, UserStats,
 };
 
+/// Contract version reported by version(); bump on every deployed change
+const CONTRACT_VERSION: u32 = 1;
+/// Ledgers per day at ~5 seconds per ledger
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// Persistent entries are bumped to live this many ledgers (~30 days)
+const TTL_EXTEND_TO_LEDGERS: u32 = 30 * DAY_IN_LEDGERS;
+/// Entries are only bumped once their remaining TTL drops below this (~29 days)
+const TTL_THRESHOLD_LEDGERS: u32 = TTL_EXTEND_TO_LEDGERS - DAY_IN_LEDGERS;
+/// Maximum house fee in basis points (10%)
+const MAX_FEE_BPS: u32 = 1000;
+/// Basis points denominator (100%)
+const BPS_DENOMINATOR: i128 = 10_000;
+/// Fixed-point scale for payout multipliers (1_000_000 = 1.0x)
+const ODDS_SCALE: i128 = 1_000_000;
+/// Outcome codes published in the ("round", "resolved") event
+const OUTCOME_DOWN: u32 = 0;
+const OUTCOME_UP: u32 = 1;
+const OUTCOME_UNCHANGED: u32 = 2;
+const OUTCOME_PRECISION: u32 = 3;
+/// Precision rounds quote prices with 4 decimals (0.2297 XLM -> 2297)
+const PRICE_SCALE: u128 = 10_000;
+/// Up/Down rounds quote prices in 7-decimal stroops (0.2297 XLM -> 2_297_000)
+const STROOP_SCALE: u128 = 10_000_000;
+/// Largest 4-decimal price accepted by Precision rounds (9999.9999 XLM)
+const MAX_PRECISION_PRICE: u128 = 99_999_999;
+/// Round phases returned by get_round_phase
+const PHASE_NONE: u32 = 0;
+const PHASE_BETTING: u32 = 1;
+const PHASE_LOCKED: u32 = 2;
+const PHASE_RESOLVABLE: u32 = 3;
+/// Role bits returned by get_role
+const ROLE_ADMIN: u32 = 1;
+const ROLE_ORACLE: u32 = 2;
+/// Fixed-point scale for inverse-distance weights (weight = WEIGHT_SCALE / (distance + 1))
+/// Larger than any valid price distance so realistic guesses keep a non-zero weight
+const WEIGHT_SCALE: u128 = 1_000_000_000_000;
+/// Default vXLM granted by mint_initial when no grant was configured (1000 vXLM)
+const DEFAULT_INITIAL_GRANT: i128 = 1000_0000000;
+/// Default ledgers after end_ledger before an unresolved round can be expired (~1 day)
+const DEFAULT_EXPIRY_GRACE: u32 = DAY_IN_LEDGERS;
+/// Default ledgers pending winnings may sit unclaimed before the admin can sweep them (~30 days)
+const DEFAULT_CLAIM_EXPIRY: u32 = 30 * DAY_IN_LEDGERS;
+/// Default ledgers between faucet claims (~1 day)
+const DEFAULT_FAUCET_COOLDOWN: u32 = DAY_IN_LEDGERS;
+/// Default timelock between enabling emergency mode and the first emergency withdrawal (~1 week)
+const DEFAULT_EMERGENCY_DELAY: u32 = 7 * DAY_IN_LEDGERS;
+/// Decimal places of vXLM amounts (1 vXLM = 10^7 stroops)
+const DECIMALS: u32 = 7;
+/// Default vXLM granted per faucet claim (100 vXLM)
+const DEFAULT_FAUCET_AMOUNT: i128 = 100_0000000;
+/// Default precision predictions accepted per round, bounding resolution cost
+const DEFAULT_MAX_PREDICTIONS: u32 = 100;
+/// Default Up/Down bettors accepted per round, bounding resolution cost
+const DEFAULT_MAX_PARTICIPANTS: u32 = 100;
+/// Number of resolved bets kept in each user's bet history
+const BET_HISTORY_SIZE: u32 = 20;
+/// Most users that can be opted in to auto-compounding at once
+const MAX_AUTO_COMPOUNDERS: u32 = 100;
+/// Number of resolved rounds kept in the round history
+const ROUND_HISTORY_SIZE: u32 = 50;
+/// Asset whose price is pulled from the oracle contract
+const ORACLE_ASSET: Symbol = symbol_short!("XLM");
+/// Ed25519 group order L, little-endian; a signature's S half must be below it
+const ED25519_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+/// Number of entries kept on the best-streak leaderboard
+const LEADERBOARD_SIZE: u32 = 10;
+
 #[contract]
 pub struct VirtualTokenContract;
 
 #[contractimpl]
 impl VirtualTokenContract {
     /// Initializes the contract with admin and oracle addresses (one-time only)
-    pub fn initialize(env: Env, admin: Address, oracle: Address) -> Result<(), ContractError> {
+    /// initial_grant: vXLM handed to each new user by mint_initial
+    /// stake_token: token contract to hold real stakes in custody; None keeps virtual balances
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+        initial_grant: i128,
+        stake_token: Option<Address>,
+    ) -> Result<(), ContractError> {
         admin.require_auth();
 
         if env.storage().persistent().has(&DataKey::Admin) {
             return Err(ContractError::AlreadyInitialized);
         }
 
+        if initial_grant <= 0 {
+            return Err(ContractError::InvalidGrant);
+        }
+
+        // Round creation and resolution must not sit behind a single key
+        if admin == oracle {
+            return Err(ContractError::AdminOracleConflict);
+        }
+
         env.storage().persistent().set(&DataKey::Admin, &admin);
         env.storage().persistent().set(&DataKey::Oracle, &oracle);
+        env.storage()
+            .persistent()
+            .set(&DataKey::InitialGrant, &initial_grant);
+        if let Some(stake_token) = stake_token {
+            env.storage()
+                .persistent()
+                .set(&DataKey::StakeToken, &stake_token);
+        }
 
         // Set default window values
         env.storage()
@@ -34,8 +135,52 @@ impl VirtualTokenContract {
         Ok(())
     }
 
+    /// Returns the contract version compiled into this binary
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replaces the contract's WASM while keeping its storage (admin only)
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("contract"), symbol_short!("upgraded")),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Returns (admin, oracle, bet_window, run_window) so clients can bootstrap in one call
+    pub fn get_config(env: Env) -> Result<(Address, Address, u32, u32), ContractError> {
+        let admin = Self::get_admin(env.clone()).ok_or(ContractError::AdminNotSet)?;
+        let oracle = Self::get_oracle(env.clone()).ok_or(ContractError::OracleNotSet)?;
+        let (bet_ledgers, run_ledgers) = Self::get_windows(env);
+
+        Ok((admin, oracle, bet_ledgers, run_ledgers))
+    }
+
+    /// Converts a 7-decimal stroop price to the 4-decimal scale used by Precision rounds
+    /// Digits beyond the fourth decimal are truncated (2_297_999 -> 2297)
+    pub fn normalize_price(_env: Env, price_stroops: u128) -> u128 {
+        price_stroops / (STROOP_SCALE / PRICE_SCALE)
+    }
+
     /// Creates a new prediction round (admin only)
-    /// mode: 0 = Up/Down (default), 1 = Precision (Legends)
+    /// mode: 0 = Up/Down (default), 1 = Precision (Legends), 2 = Precision weighted by distance
+    /// start_price is in stroops for Up/Down rounds and 4-decimal scale for Precision rounds
+    /// With a stake token, the previous round must be resolved or refunded first
     pub fn create_round(
         env: Env,
         start_price: u128,
@@ -48,17 +193,17 @@ impl VirtualTokenContract {
         // Default to Up/Down mode (0) if not specified
         let mode_value = mode.unwrap_or(0);
 
-        // Validate mode is either 0 or 1
-        if mode_value > 1 {
-            return Err(ContractError::InvalidMode);
-        }
-
-        let round_mode = if mode_value == 0 {
-            RoundMode::UpDown
-        } else {
-            RoundMode::Precision
+        let round_mode = match mode_value {
+            0 => RoundMode::UpDown,
+            1 => RoundMode::Precision,
+            2 => RoundMode::PrecisionWeighted,
+            _ => return Err(ContractError::InvalidMode),
         };
 
+        if round_mode != RoundMode::UpDown && start_price > MAX_PRECISION_PRICE {
+            return Err(ContractError::InvalidPriceScale);
+        }
+
         let admin: Address = env
             .storage()
             .persistent()
@@ -67,6 +212,13 @@ impl VirtualTokenContract {
 
         admin.require_auth();
 
+        // Replacing an unresolved round would strand the tokens staked in it
+        if Self::get_stake_token(env.clone()).is_some()
+            && env.storage().persistent().has(&DataKey::ActiveRound)
+        {
+            return Err(ContractError::RoundInProgress);
+        }
+
         // Get configured windows (with defaults)
         let bet_ledgers: u32 = env
             .storage()
@@ -87,6 +239,11 @@ impl VirtualTokenContract {
             .checked_add(run_ledgers)
             .ok_or(ContractError::Overflow)?;
 
+        // Guard against degenerate rounds from inconsistent window config
+        if !(start_ledger < bet_end_ledger && bet_end_ledger < end_ledger) {
+            return Err(ContractError::InvalidDuration);
+        }
+
         let round = Round {
             price_start: start_price,
             start_ledger,
@@ -100,12 +257,21 @@ impl VirtualTokenContract {
         env.storage()
             .persistent()
             .set(&DataKey::ActiveRound, &round);
+        Self::_extend_ttl(&env, &DataKey::ActiveRound);
 
         // Clear previous round's positions based on mode
         env.storage().persistent().remove(&DataKey::UpDownPositions);
         env.storage()
             .persistent()
             .remove(&DataKey::PrecisionPositions);
+        env.storage().persistent().remove(&DataKey::PrecisionIndex);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PriceSubmissions);
+
+        if round_mode == RoundMode::UpDown && !Self::is_paused(env.clone()) {
+            Self::_auto_compound(&env)?;
+        }
 
         // Emit round creation event with mode
         #[allow(deprecated)]
@@ -117,23 +283,33 @@ impl VirtualTokenContract {
         Ok(())
     }
 
-    /// Returns the currently active round, if any
-    pub fn get_active_round(env: Env) -> Option<Round> {
-        env.storage().persistent().get(&DataKey::ActiveRound)
-    }
+    /// Cancels the active round and refunds every stake (admin only)
+    /// Refunds are credited to pending winnings; user stats are left untouched
+    pub fn cancel_round(env: Env) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-    pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().persistent().get(&DataKey::Admin)
-    }
+        admin.require_auth();
 
-    pub fn get_oracle(env: Env) -> Option<Address> {
-        env.storage().persistent().get(&DataKey::Oracle)
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        Self::_refund_round(&env, &round)
     }
 
-    /// Sets the betting and execution windows (admin only)
-    /// bet_ledgers: Number of ledgers users can place bets
-    /// run_ledgers: Total number of ledgers before round can be resolved
-    pub fn set_windows(env: Env, bet_ledgers: u32, run_ledgers: u32) -> Result<(), ContractError> {
+    /// Pushes back the active round's bet and end ledgers, e.g. during an oracle delay (admin only)
+    /// Rejected once betting has closed so a locked round is never reopened
+    pub fn extend_active_round(
+        env: Env,
+        extra_bet_ledgers: u32,
+        extra_run_ledgers: u32,
+    ) -> Result<(), ContractError> {
         let admin: Address = env
             .storage()
             .persistent()
@@ -142,62 +318,54 @@ impl VirtualTokenContract {
 
         admin.require_auth();
 
-        // Validate both values are positive
-        if bet_ledgers == 0 || run_ledgers == 0 {
-            return Err(ContractError::InvalidDuration);
+        let mut round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        if !Self::_betting_open(&env, &round) {
+            return Err(ContractError::RoundEnded);
         }
 
-        // Validate bet window closes before run window ends
-        if bet_ledgers >= run_ledgers {
+        round.bet_end_ledger = round
+            .bet_end_ledger
+            .checked_add(extra_bet_ledgers)
+            .ok_or(ContractError::Overflow)?;
+        round.end_ledger = round
+            .end_ledger
+            .checked_add(extra_run_ledgers)
+            .ok_or(ContractError::Overflow)?;
+
+        // Bet window must still close before the run window ends
+        if round.bet_end_ledger >= round.end_ledger {
             return Err(ContractError::InvalidDuration);
         }
 
         env.storage()
             .persistent()
-            .set(&DataKey::BetWindowLedgers, &bet_ledgers);
-        env.storage()
-            .persistent()
-            .set(&DataKey::RunWindowLedgers, &run_ledgers);
+            .set(&DataKey::ActiveRound, &round);
+        Self::_extend_ttl(&env, &DataKey::ActiveRound);
 
-        // Emit event
         #[allow(deprecated)]
         env.events().publish(
-            (symbol_short!("windows"), symbol_short!("updated")),
-            (bet_ledgers, run_ledgers),
+            (symbol_short!("round"), symbol_short!("extended")),
+            (round.bet_end_ledger, round.end_ledger),
         );
 
         Ok(())
     }
 
-    /// Returns user statistics (wins, losses, streaks)
-    pub fn get_user_stats(env: Env, user: Address) -> UserStats {
-        let key = DataKey::UserStats(user);
-        env.storage().persistent().get(&key).unwrap_or(UserStats {
-            total_wins: 0,
-            total_losses: 0,
-            current_streak: 0,
-            best_streak: 0,
-        })
-    }
-
-    /// Returns user's claimable winnings
-    pub fn get_pending_winnings(env: Env, user: Address) -> i128 {
-        let key = DataKey::PendingWinnings(user);
-        env.storage().persistent().get(&key).unwrap_or(0)
-    }
-
-    /// Places a bet on the active round (Up/Down mode only)
-    pub fn place_bet(
-        env: Env,
-        user: Address,
-        amount: i128,
-        side: BetSide,
-    ) -> Result<(), ContractError> {
-        user.require_auth();
+    /// Closes betting on the active round immediately, e.g. on breaking news (admin only)
+    /// Only bet_end_ledger moves; end_ledger and so resolution timing are unchanged
+    pub fn lock_betting(env: Env) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-        if amount <= 0 {
-            return Err(ContractError::InvalidBetAmount);
-        }
+        admin.require_auth();
 
         let mut round: Round = env
             .storage()
@@ -205,565 +373,4639 @@ impl VirtualTokenContract {
             .get(&DataKey::ActiveRound)
             .ok_or(ContractError::NoActiveRound)?;
 
-        // Verify round is in Up/Down mode
-        if round.mode != RoundMode::UpDown {
-            return Err(ContractError::WrongModeForPrediction);
-        }
-
-        let current_ledger = env.ledger().sequence();
-        if current_ledger >= round.bet_end_ledger {
+        if !Self::_betting_open(&env, &round) {
             return Err(ContractError::RoundEnded);
         }
 
-        let user_balance = Self::balance(env.clone(), user.clone());
-        if user_balance < amount {
-            return Err(ContractError::InsufficientBalance);
-        }
-
-        // Use UpDownPositions storage for Up/Down mode
-        let mut positions: Map<Address, UserPosition> = env
-            .storage()
+        round.bet_end_ledger = env.ledger().sequence();
+        env.storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(&env));
-
-        if positions.contains_key(user.clone()) {
-            return Err(ContractError::AlreadyBet);
-        }
+            .set(&DataKey::ActiveRound, &round);
+        Self::_extend_ttl(&env, &DataKey::ActiveRound);
 
-        let new_balance = user_balance
-            .checked_sub(amount)
-            .ok_or(ContractError::Overflow)?;
-        Self::_set_balance(&env, user.clone(), new_balance);
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("locked")),
+            round.bet_end_ledger,
+        );
 
-        let position = UserPosition {
-            amount,
-            side: side.clone(),
-        };
-        positions.set(user.clone(), position);
+        Ok(())
+    }
 
-        match side {
-            BetSide::Up => {
-                round.pool_up = round
-                    .pool_up
-                    .checked_add(amount)
-                    .ok_or(ContractError::Overflow)?;
-            }
-            BetSide::Down => {
-                round.pool_down = round
-                    .pool_down
-                    .checked_add(amount)
-                    .ok_or(ContractError::Overflow)?;
-            }
-        }
+    /// Refunds a round the oracle failed to resolve in time (callable by anyone)
+    /// Allowed past end_ledger plus the shorter of the expiry grace and the max resolve delay,
+    /// or past the announced resolution's deadline_ledger while one is pending
+    pub fn refund_expired_round(env: Env) -> Result<(), ContractError> {
+        Self::_refund_expired_round(&env)
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::UpDownPositions, &positions);
-        env.storage()
-            .persistent()
-            .set(&DataKey::ActiveRound, &round);
+    /// Refunds and clears a round that was never resolved (callable by anyone)
+    /// Same deadline and effect as refund_expired_round; no price is consulted
+    pub fn expire_round(env: Env) -> Result<(), ContractError> {
+        Self::_refund_expired_round(&env)
+    }
 
-        // Also keep legacy Positions storage for backwards compatibility
-        let mut legacy_positions: Map<Address, UserPosition> = env
+    /// Sets the grace period in ledgers after end_ledger before a round can be expired (admin only)
+    pub fn set_expiry_grace(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::Positions)
-            .unwrap_or(Map::new(&env));
-        legacy_positions.set(user, UserPosition { amount, side });
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_expiry_grace(env.clone());
+
         env.storage()
             .persistent()
-            .set(&DataKey::Positions, &legacy_positions);
+            .set(&DataKey::ExpiryGrace, &ledgers);
+
+        Self::_publish_config_change(
+            &env,
+            "expiry_grace",
+            old,
+            Self::get_expiry_grace(env.clone()),
+        );
 
         Ok(())
     }
 
-    /// Places a precision prediction on the active round (Precision/Legends mode only)
-    /// predicted_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
-    pub fn place_precision_prediction(
-        env: Env,
-        user: Address,
-        amount: i128,
-        predicted_price: u128,
-    ) -> Result<(), ContractError> {
-        user.require_auth();
-
-        if amount <= 0 {
-            return Err(ContractError::InvalidBetAmount);
-        }
-
-        // Validate price scale (must be 4 decimal places, max value 9999 for 0.9999)
-        // Reasonable max: 99999999 (9999.9999 XLM)
-        if predicted_price > 99_999_999 {
-            return Err(ContractError::InvalidPriceScale);
-        }
+    /// Returns the expiry grace period in ledgers (~1 day if unset)
+    pub fn get_expiry_grace(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ExpiryGrace)
+            .unwrap_or(DEFAULT_EXPIRY_GRACE)
+    }
 
+    /// Refunds the active round once current_ledger is past its refund deadline
+    fn _refund_expired_round(env: &Env) -> Result<(), ContractError> {
         let round: Round = env
             .storage()
             .persistent()
             .get(&DataKey::ActiveRound)
             .ok_or(ContractError::NoActiveRound)?;
 
-        // Verify round is in Precision mode
-        if round.mode != RoundMode::Precision {
-            return Err(ContractError::WrongModeForPrediction);
-        }
-
-        let current_ledger = env.ledger().sequence();
-        if current_ledger >= round.bet_end_ledger {
-            return Err(ContractError::RoundEnded);
-        }
-
-        let user_balance = Self::balance(env.clone(), user.clone());
-        if user_balance < amount {
-            return Err(ContractError::InsufficientBalance);
+        if env.ledger().sequence() <= Self::_refund_deadline(env, &round) {
+            return Err(ContractError::RoundNotEnded);
         }
 
-        // Check if user already has a prediction in this round
-        let mut predictions: Vec<PrecisionPrediction> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Vec::new(&env));
+        Self::_refund_round(env, &round)
+    }
 
-        for i in 0..predictions.len() {
-            if let Some(pred) = predictions.get(i) {
-                if pred.user == user {
-                    return Err(ContractError::AlreadyBet);
-                }
-            }
+    /// Returns the last ledger before anyone may refund the round: an announced resolution's
+    /// deadline_ledger while one is pending, otherwise end_ledger plus the expiry grace or the
+    /// max resolve delay, whichever is shorter
+    fn _refund_deadline(env: &Env, round: &Round) -> u32 {
+        if let Some(pending) = Self::get_pending_resolution(env.clone()) {
+            return pending.deadline_ledger;
         }
 
-        // Deduct balance
-        let new_balance = user_balance
-            .checked_sub(amount)
-            .ok_or(ContractError::Overflow)?;
-        Self::_set_balance(&env, user.clone(), new_balance);
-
-        // Store prediction
-        let prediction = PrecisionPrediction {
-            user: user.clone(),
-            predicted_price,
-            amount,
+        let grace = Self::get_expiry_grace(env.clone());
+        let delay = match Self::get_max_resolve_delay(env.clone()) {
+            Some(max_delay) => grace.min(max_delay),
+            None => grace,
         };
-        predictions.push_back(prediction);
+        round.end_ledger.saturating_add(delay)
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::PrecisionPositions, &predictions);
+    /// Refunds every stake in the round to pending winnings and clears round storage
+    fn _refund_round(env: &Env, round: &Round) -> Result<(), ContractError> {
+        Self::_refund_positions(env)?;
+        Self::_clear_round(env);
 
-        // Emit event for precision prediction
         #[allow(deprecated)]
         env.events().publish(
-            (symbol_short!("predict"), symbol_short!("price")),
-            (user, predicted_price, round.start_ledger),
+            (symbol_short!("round"), symbol_short!("cancelled")),
+            round.start_ledger,
         );
 
         Ok(())
     }
 
-    /// Alias for place_precision_prediction - allows users to submit exact price predictions
-    /// guessed_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
-    pub fn predict_price(
-        env: Env,
-        user: Address,
-        guessed_price: u128,
-        amount: i128,
-    ) -> Result<(), ContractError> {
-        Self::place_precision_prediction(env, user, amount, guessed_price)
-    }
-
-    /// Returns user's position in the current round (Up/Down mode)
-    pub fn get_user_position(env: Env, user: Address) -> Option<UserPosition> {
+    /// Credits every Up/Down and precision stake back to pending winnings
+    fn _refund_positions(env: &Env) -> Result<(), ContractError> {
         let positions: Map<Address, UserPosition> = env
             .storage()
             .persistent()
             .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(&env));
+            .unwrap_or(Map::new(env));
+        Self::_record_refunds(env, positions)?;
 
-        positions.get(user)
+        let predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(env));
+        Self::_record_precision_refunds(env, predictions)
     }
 
-    /// Returns user's precision prediction in the current round (Precision mode)
-    pub fn get_user_precision_prediction(env: Env, user: Address) -> Option<PrecisionPrediction> {
+    /// Returns the number of positions placed in the active round across both modes
+    fn _participant_count(env: &Env) -> u32 {
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
         let predictions: Vec<PrecisionPrediction> = env
             .storage()
             .persistent()
             .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
 
-        for i in 0..predictions.len() {
-            if let Some(pred) = predictions.get(i) {
-                if pred.user == user {
-                    return Some(pred);
-                }
-            }
-        }
-        None
+        positions.len().saturating_add(predictions.len())
     }
 
-    /// Returns all precision predictions for the current round
-    pub fn get_precision_predictions(env: Env) -> Vec<PrecisionPrediction> {
+    /// Removes the active round and all of its positions from storage
+    fn _clear_round(env: &Env) {
+        env.storage().persistent().remove(&DataKey::ActiveRound);
+        env.storage().persistent().remove(&DataKey::Positions);
+        env.storage().persistent().remove(&DataKey::UpDownPositions);
         env.storage()
             .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Vec::new(&env))
+            .remove(&DataKey::PrecisionPositions);
+        env.storage().persistent().remove(&DataKey::PrecisionIndex);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PriceSubmissions);
+        env.storage()
+            .persistent()
+            .remove(&ExtDataKey::PendingResolution);
     }
 
-    /// Returns all Up/Down positions for the current round
-    pub fn get_updown_positions(env: Env) -> Map<Address, UserPosition> {
+    /// Returns true once the oracle's resolution window for the round has passed
+    fn _resolution_window_expired(env: &Env, round: &Round) -> bool {
+        let max_delay: Option<u32> = env.storage().persistent().get(&DataKey::MaxResolveDelay);
+        match max_delay {
+            Some(delay) => env.ledger().sequence() > round.end_ledger.saturating_add(delay),
+            None => false,
+        }
+    }
+
+    /// Returns the currently active round, if any
+    pub fn get_active_round(env: Env) -> Option<Round> {
+        env.storage().persistent().get(&DataKey::ActiveRound)
+    }
+
+    /// Returns (ledgers_until_bet_close, ledgers_until_end) for the active round
+    /// Both values saturate at 0 once passed
+    pub fn get_time_remaining(env: Env) -> Option<(u32, u32)> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        let current_ledger = env.ledger().sequence();
+
+        Some((
+            round.bet_end_ledger.saturating_sub(current_ledger),
+            round.end_ledger.saturating_sub(current_ledger),
+        ))
+    }
+
+    /// Returns the active round together with the current ledger in one call
+    pub fn get_round_summary(env: Env) -> Option<RoundSummary> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        let current_ledger = env.ledger().sequence();
+        let betting_open = Self::_betting_open(&env, &round);
+
+        Some(RoundSummary {
+            price_start: round.price_start,
+            pool_up: round.pool_up,
+            pool_down: round.pool_down,
+            mode: round.mode,
+            start_ledger: round.start_ledger,
+            bet_end_ledger: round.bet_end_ledger,
+            end_ledger: round.end_ledger,
+            current_ledger,
+            betting_open,
+        })
+    }
+
+    /// Returns the active round's mode (0 = Up/Down, 1 = Precision, 2 = Precision weighted)
+    pub fn get_round_mode(env: Env) -> Option<u32> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        Some(round.mode as u32)
+    }
+
+    /// Returns true while the active round accepts bets (false when there is none)
+    pub fn is_betting_open(env: Env) -> bool {
+        let round: Option<Round> = env.storage().persistent().get(&DataKey::ActiveRound);
+        match round {
+            Some(round) => Self::_betting_open(&env, &round),
+            None => false,
+        }
+    }
+
+    /// Returns the active round's phase
+    /// 0 = no round, 1 = betting open, 2 = locked (betting closed, not ended), 3 = resolvable
+    pub fn get_round_phase(env: Env) -> u32 {
+        let round: Option<Round> = env.storage().persistent().get(&DataKey::ActiveRound);
+        match round {
+            None => PHASE_NONE,
+            Some(round) if Self::_betting_open(&env, &round) => PHASE_BETTING,
+            Some(round) if env.ledger().sequence() < round.end_ledger => PHASE_LOCKED,
+            Some(_) => PHASE_RESOLVABLE,
+        }
+    }
+
+    /// Bets are accepted while the current ledger is before bet_end_ledger
+    fn _betting_open(env: &Env, round: &Round) -> bool {
+        env.ledger().sequence() < round.bet_end_ledger
+    }
+
+    /// Returns true once the active round has ended and can still be resolved
+    pub fn can_resolve(env: Env) -> bool {
+        let round: Option<Round> = env.storage().persistent().get(&DataKey::ActiveRound);
+        match round {
+            Some(round) => {
+                env.ledger().sequence() >= round.end_ledger
+                    && !Self::_resolution_window_expired(&env, &round)
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Admin)
+    }
+
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Oracle)
+    }
+
+    /// Returns the roles `who` holds as a bitmask: 1 = admin, 2 = oracle (0 = neither, 3 = both)
+    pub fn get_role(env: Env, who: Address) -> u32 {
+        let mut role = 0;
+        if Self::get_admin(env.clone()) == Some(who.clone()) {
+            role |= ROLE_ADMIN;
+        }
+        if Self::get_oracle(env) == Some(who) {
+            role |= ROLE_ORACLE;
+        }
+        role
+    }
+
+    /// Proposes a new admin (admin only)
+    /// The proposal takes effect once the new admin calls accept_admin
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        if Self::get_oracle(env.clone()) == Some(new_admin.clone()) {
+            return Err(ContractError::AdminOracleConflict);
+        }
+
         env.storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(&env))
+            .set(&DataKey::PendingAdmin, &new_admin);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("admin"), symbol_short!("proposed")),
+            new_admin,
+        );
+
+        Ok(())
     }
 
-    /// Resolves the round with final price (oracle only)
-    /// Mode 0 (Up/Down): Winners split losers' pool proportionally; ties get refunds
-    /// Mode 1 (Precision/Legends): Closest guess wins full pot; ties split evenly
-    pub fn resolve_round(
-        env: Env,
-        payload: crate::types::OraclePayload,
-    ) -> Result<(), ContractError> {
-        if payload.price == 0 {
-            return Err(ContractError::InvalidPrice);
+    /// Accepts a pending admin proposal (pending admin only)
+    pub fn accept_admin(env: Env) -> Result<(), ContractError> {
+        let pending: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(ContractError::NoPendingAdmin)?;
+
+        pending.require_auth();
+
+        // The oracle may have been rotated to the pending admin since the proposal
+        if Self::get_oracle(env.clone()) == Some(pending.clone()) {
+            return Err(ContractError::AdminOracleConflict);
         }
 
-        let oracle: Address = env
+        env.storage().persistent().set(&DataKey::Admin, &pending);
+        env.storage().persistent().remove(&DataKey::PendingAdmin);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("admin"), symbol_short!("accepted")), pending);
+
+        Ok(())
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::PendingAdmin)
+    }
+
+    /// Rotates the oracle address (admin only)
+    pub fn set_oracle(env: Env, new_oracle: Address) -> Result<(), ContractError> {
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::Oracle)
-            .ok_or(ContractError::OracleNotSet)?;
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-        oracle.require_auth();
+        admin.require_auth();
 
-        let round: Round = env
+        if new_oracle == admin {
+            return Err(ContractError::AdminOracleConflict);
+        }
+
+        let old = Self::get_oracle(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Oracle, &new_oracle);
+
+        Self::_publish_config_change(&env, "oracle", old, Self::get_oracle(env.clone()));
+
+        Ok(())
+    }
+
+    /// Sets the betting and execution windows (admin only)
+    /// bet_ledgers: Number of ledgers users can place bets
+    /// run_ledgers: Total number of ledgers before round can be resolved
+    /// Changes apply to the next create_round; rejected while a round is active
+    pub fn set_windows(env: Env, bet_ledgers: u32, run_ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::ActiveRound)
-            .ok_or(ContractError::NoActiveRound)?;
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-        // Verify round ID matches to prevent cross-round replays
-        if payload.round_id != round.start_ledger {
-            return Err(ContractError::InvalidOracleRound);
+        admin.require_auth();
+
+        let old = Self::get_windows(env.clone());
+
+        // The active round already captured its windows
+        if env.storage().persistent().has(&DataKey::ActiveRound) {
+            return Err(ContractError::RoundInProgress);
         }
 
-        // Verify data freshness (max 300 seconds / 5 minutes old)
-        let current_time = env.ledger().timestamp();
-        if current_time > payload.timestamp + 300 {
-            return Err(ContractError::StaleOracleData);
+        // Validate both values are positive
+        if bet_ledgers == 0 || run_ledgers == 0 {
+            return Err(ContractError::InvalidDuration);
         }
 
-        // Verify round has reached end_ledger
-        let current_ledger = env.ledger().sequence();
-        if current_ledger < round.end_ledger {
-            return Err(ContractError::RoundNotEnded);
+        // Validate bet window closes before run window ends
+        if bet_ledgers >= run_ledgers {
+            return Err(ContractError::InvalidDuration);
         }
 
-        // Branch based on round mode
-        match round.mode {
-            RoundMode::UpDown => {
-                Self::_resolve_updown_mode(&env, &round, payload.price)?;
-            }
-            RoundMode::Precision => {
-                Self::_resolve_precision_mode(&env, payload.price)?;
-            }
+        env.storage()
+            .persistent()
+            .set(&DataKey::BetWindowLedgers, &bet_ledgers);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RunWindowLedgers, &run_ledgers);
+
+        Self::_publish_config_change(&env, "windows", old, Self::get_windows(env.clone()));
+
+        Ok(())
+    }
+
+    /// Returns the configured (bet_ledgers, run_ledgers) windows
+    pub fn get_windows(env: Env) -> (u32, u32) {
+        let bet_ledgers: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BetWindowLedgers)
+            .unwrap_or(6);
+        let run_ledgers: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RunWindowLedgers)
+            .unwrap_or(12);
+
+        (bet_ledgers, run_ledgers)
+    }
+
+    /// Sets how many ledgers after end_ledger the oracle may still resolve (admin only)
+    /// Past this delay the round can only be refunded via refund_expired_round
+    pub fn set_max_resolve_delay(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_max_resolve_delay(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxResolveDelay, &ledgers);
+
+        Self::_publish_config_change(
+            &env,
+            "max_resolve_delay",
+            old,
+            Self::get_max_resolve_delay(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the max resolve delay in ledgers (None means unlimited)
+    pub fn get_max_resolve_delay(env: Env) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::MaxResolveDelay)
+    }
+
+    /// Returns the ledger at which the most recent round was resolved
+    pub fn get_last_resolved_ledger(env: Env) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::LastResolvedLedger)
+    }
+
+    /// Sets the house fee taken from the losing pool (admin only)
+    /// bps: Fee in basis points, at most 1000 (10%)
+    pub fn set_fee_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_fee_bps(env.clone());
+
+        if bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidFee);
+        }
+
+        env.storage().persistent().set(&DataKey::FeeBps, &bps);
+
+        Self::_publish_config_change(&env, "fee_bps", old, Self::get_fee_bps(env.clone()));
+
+        Ok(())
+    }
+
+    /// Returns the configured house fee in basis points (0 if unset)
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Sets the share of the losing pool paid to the oracle that resolves a round (admin only)
+    /// bps: Reward in basis points, at most 1000 (10%), credited to the oracle's pending winnings
+    /// Only Up/Down rounds settled by the oracle itself pay it: resolve_round, resolve_and_pay
+    /// and confirm_resolution. finalize_resolution, resolve_from_oracle_contract and
+    /// resolve_with_signature pay no reward
+    pub fn set_resolver_reward_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_resolver_reward_bps(env.clone());
+
+        if bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidFee);
         }
 
-        // Clean up storage
-        env.storage().persistent().remove(&DataKey::ActiveRound);
-        env.storage().persistent().remove(&DataKey::Positions);
-        env.storage().persistent().remove(&DataKey::UpDownPositions);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::PrecisionPositions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ResolverRewardBps, &bps);
+
+        Self::_publish_config_change(
+            &env,
+            "resolver_reward_bps",
+            old,
+            Self::get_resolver_reward_bps(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the resolver reward in basis points (0 if unset)
+    pub fn get_resolver_reward_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ResolverRewardBps)
+            .unwrap_or(0)
+    }
+
+    /// Sets the address credited with house fees (admin only)
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_treasury(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Treasury, &treasury);
+
+        Self::_publish_config_change(&env, "treasury", old, Self::get_treasury(env.clone()));
+
+        Ok(())
+    }
+
+    /// Returns the treasury address, falling back to the admin if unset
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Treasury)
+            .or_else(|| Self::get_admin(env.clone()))
+    }
+
+    /// Returns the total house fees collected since deployment
+    pub fn get_accumulated_fees(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccumulatedFees)
+            .unwrap_or(0)
+    }
+
+    /// Moves `amount` of collected fees out of the treasury's balance to `to` (admin only)
+    /// With a stake token the treasury's real tokens are transferred, which also needs the
+    /// treasury's auth when it is not the admin. Returns the treasury's remaining balance
+    pub fn withdraw_treasury(env: Env, to: Address, amount: i128) -> Result<i128, ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let treasury = Self::get_treasury(env.clone()).ok_or(ContractError::AdminNotSet)?;
+        let remaining = match Self::get_stake_token(env.clone()) {
+            Some(stake_token) => {
+                let client = token::Client::new(&env, &stake_token);
+                if client.balance(&treasury) < amount {
+                    return Err(ContractError::InsufficientBalance);
+                }
+                client.transfer(&treasury, &to, &amount);
+                client.balance(&treasury)
+            }
+            None => {
+                let treasury_balance = Self::balance(env.clone(), treasury.clone());
+                if treasury_balance < amount {
+                    return Err(ContractError::InsufficientBalance);
+                }
+                if to != treasury {
+                    let new_to_balance = Self::balance(env.clone(), to.clone())
+                        .checked_add(amount)
+                        .ok_or(ContractError::Overflow)?;
+                    Self::_set_balance(&env, treasury.clone(), treasury_balance - amount);
+                    Self::_set_balance(&env, to.clone(), new_to_balance);
+                }
+                Self::balance(env.clone(), treasury)
+            }
+        };
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("treasury"), symbol_short!("withdrawn")),
+            (to, amount, remaining),
+        );
+
+        Ok(remaining)
+    }
+
+    /// Pauses new bets and predictions (admin only)
+    /// Resolution, claims and withdrawals keep working so users can exit
+    pub fn pause(env: Env) -> Result<(), ContractError> {
+        Self::_set_paused(&env, true)
+    }
+
+    /// Resumes betting after a pause (admin only)
+    pub fn unpause(env: Env) -> Result<(), ContractError> {
+        Self::_set_paused(&env, false)
+    }
+
+    /// Returns true if betting is currently paused
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    fn _set_paused(env: &Env, paused: bool) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::Paused, &paused);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("paused"), symbol_short!("updated")), paused);
+
+        Ok(())
+    }
+
+    /// Enables or disables emergency mode (admin only)
+    /// Enabling starts the emergency delay timelock, after which anyone can emergency_withdraw
+    pub fn set_emergency_mode(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let unlock_ledger = if enabled {
+            let unlock_ledger = env
+                .ledger()
+                .sequence()
+                .saturating_add(Self::get_emergency_delay(env.clone()));
+            env.storage()
+                .persistent()
+                .set(&ExtDataKey::EmergencyMode, &unlock_ledger);
+            Some(unlock_ledger)
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&ExtDataKey::EmergencyMode);
+            None
+        };
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("emergency"), symbol_short!("updated")),
+            unlock_ledger,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the ledger from which emergency withdrawals are allowed, if emergency mode is on
+    pub fn get_emergency_unlock_ledger(env: Env) -> Option<u32> {
+        env.storage().persistent().get(&ExtDataKey::EmergencyMode)
+    }
+
+    /// Sets the emergency mode timelock in ledgers (admin only)
+    /// Only affects emergency mode enabled afterwards
+    pub fn set_emergency_delay(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_emergency_delay(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::EmergencyDelay, &ledgers);
+
+        Self::_publish_config_change(
+            &env,
+            "emergency_delay",
+            old,
+            Self::get_emergency_delay(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the emergency mode timelock in ledgers (~1 week if unset)
+    pub fn get_emergency_delay(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::EmergencyDelay)
+            .unwrap_or(DEFAULT_EMERGENCY_DELAY)
+    }
+
+    /// Reclaims everything the user has in the contract once emergency mode is unlocked
+    /// Refunds any open position in the active round regardless of its windows, pays out
+    /// pending winnings, and returns the user's resulting balance. Works while paused
+    pub fn emergency_withdraw(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let unlock_ledger = Self::get_emergency_unlock_ledger(env.clone())
+            .ok_or(ContractError::EmergencyNotActive)?;
+        if env.ledger().sequence() < unlock_ledger {
+            return Err(ContractError::EmergencyTimelockActive);
+        }
+
+        let mut refunded = 0;
+        let round: Option<Round> = env.storage().persistent().get(&DataKey::ActiveRound);
+        if let Some(round) = round {
+            if round.mode == RoundMode::UpDown {
+                if Self::get_user_position(env.clone(), user.clone()).is_some() {
+                    refunded = Self::_take_from_position(&env, round, user.clone(), None)?;
+                }
+            } else if Self::_precision_index(&env).contains_key(user.clone()) {
+                refunded = Self::_remove_precision_prediction(&env, user.clone())?;
+            }
+        }
+
+        let balance = Self::_pay_pending(&env, &user)?;
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("emergency"), symbol_short!("withdrawn")),
+            (user, refunded, balance),
+        );
+
+        Ok(balance)
+    }
+
+    /// Publishes ("config", "changed") with (setting, old, new) so monitors can track governance
+    fn _publish_config_change<T: IntoVal<Env, Val>>(env: &Env, setting: &str, old: T, new: T) {
+        let (old, new): (Val, Val) = (old.into_val(env), new.into_val(env));
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("config"), symbol_short!("changed")),
+            (Symbol::new(env, setting), old, new),
+        );
+    }
+
+    /// Sets the precision tolerance band (admin only)
+    /// Guesses within `tolerance` of the final price split the pot by stake; 0 disables the band
+    pub fn set_precision_tolerance(env: Env, tolerance: u128) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_precision_tolerance(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrecisionTolerance, &tolerance);
+
+        Self::_publish_config_change(
+            &env,
+            "precision_tolerance",
+            old,
+            Self::get_precision_tolerance(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the precision tolerance band (0 if unset)
+    pub fn get_precision_tolerance(env: Env) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PrecisionTolerance)
+            .unwrap_or(0)
+    }
+
+    /// Sets how tied Precision guesses share the pot (admin only)
+    pub fn set_tie_break_mode(env: Env, mode: TieBreakMode) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_tie_break_mode(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TieBreakMode, &mode);
+
+        Self::_publish_config_change(
+            &env,
+            "tie_break_mode",
+            old,
+            Self::get_tie_break_mode(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Sets how proportional Up/Down payout shares are rounded (admin only)
+    pub fn set_rounding_mode(env: Env, mode: RoundingMode) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_rounding_mode(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::RoundingMode, &mode);
+
+        Self::_publish_config_change(
+            &env,
+            "rounding_mode",
+            old,
+            Self::get_rounding_mode(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns how proportional Up/Down payout shares are rounded (Floor if unset)
+    pub fn get_rounding_mode(env: Env) -> RoundingMode {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::RoundingMode)
+            .unwrap_or(RoundingMode::Floor)
+    }
+
+    /// Returns how tied Precision guesses share the pot (Split if unset)
+    pub fn get_tie_break_mode(env: Env) -> TieBreakMode {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TieBreakMode)
+            .unwrap_or(TieBreakMode::Split)
+    }
+
+    /// Sets what happens to an Up/Down pot when the price is unchanged (admin only)
+    pub fn set_draw_policy(env: Env, policy: DrawPolicy) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_draw_policy(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::DrawPolicy, &policy);
+
+        Self::_publish_config_change(&env, "draw_policy", old, Self::get_draw_policy(env.clone()));
+
+        Ok(())
+    }
+
+    /// Returns what happens to an Up/Down pot when the price is unchanged (Refund if unset)
+    pub fn get_draw_policy(env: Env) -> DrawPolicy {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DrawPolicy)
+            .unwrap_or(DrawPolicy::Refund)
+    }
+
+    /// Sets the minimum number of positions a round needs to resolve (admin only)
+    /// Rounds with fewer positions are refunded at resolution; 0 disables the rule
+    pub fn set_min_participants(env: Env, min: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_min_participants(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MinParticipants, &min);
+
+        Self::_publish_config_change(
+            &env,
+            "min_participants",
+            old,
+            Self::get_min_participants(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the minimum number of positions a round needs to resolve (0 if unset)
+    pub fn get_min_participants(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MinParticipants)
+            .unwrap_or(0)
+    }
+
+    /// Sets the Up/Down dead zone in basis points of the start price (admin only)
+    /// Moves that do not exceed it resolve as unchanged and are refunded; 0 disables it
+    pub fn set_price_move_threshold_bps(env: Env, threshold_bps: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_price_move_threshold_bps(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriceMoveThresholdBps, &threshold_bps);
+
+        Self::_publish_config_change(
+            &env,
+            "price_move_threshold_bps",
+            old,
+            Self::get_price_move_threshold_bps(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Sets how many precision predictions a single round accepts (admin only)
+    /// Must be at least 1; there is no unlimited setting since payouts iterate every entry
+    pub fn set_max_predictions(env: Env, max: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        if max == 0 {
+            return Err(ContractError::InvalidRoundCap);
+        }
+
+        let old = Self::get_max_predictions(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxPredictions, &max);
+
+        Self::_publish_config_change(
+            &env,
+            "max_predictions",
+            old,
+            Self::get_max_predictions(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns how many precision predictions a single round accepts (default 100)
+    pub fn get_max_predictions(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxPredictions)
+            .unwrap_or(DEFAULT_MAX_PREDICTIONS)
+    }
+
+    /// Sets how many Up/Down bettors a single round accepts (admin only)
+    /// Zero is rejected rather than read as unlimited, keeping settlement bounded
+    pub fn set_max_participants(env: Env, max: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        if max == 0 {
+            return Err(ContractError::InvalidRoundCap);
+        }
+
+        let old = Self::get_max_participants(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxParticipants, &max);
+
+        Self::_publish_config_change(
+            &env,
+            "max_participants",
+            old,
+            Self::get_max_participants(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns how many Up/Down bettors a single round accepts (default 100)
+    pub fn get_max_participants(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxParticipants)
+            .unwrap_or(DEFAULT_MAX_PARTICIPANTS)
+    }
+
+    /// Sets the ledgers a user must wait between consecutive bets (admin only); 0 disables it
+    pub fn set_bet_cooldown(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_bet_cooldown(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BetCooldown, &ledgers);
+
+        Self::_publish_config_change(
+            &env,
+            "bet_cooldown",
+            old,
+            Self::get_bet_cooldown(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the bet cooldown in ledgers (0 if disabled)
+    pub fn get_bet_cooldown(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BetCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Sets how many ledgers an announced resolution stays under review before
+    /// the oracle may confirm it (admin only)
+    /// While non-zero, rounds settle only through announce_resolution / confirm_resolution
+    pub fn set_review_window(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_review_window(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::ReviewWindow, &ledgers);
+
+        Self::_publish_config_change(
+            &env,
+            "review_window",
+            old,
+            Self::get_review_window(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the resolution review window in ledgers (0 if unset)
+    pub fn get_review_window(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::ReviewWindow)
+            .unwrap_or(0)
+    }
+
+    /// Sets the largest final price move from price_start accepted at resolution,
+    /// in basis points (admin only); 0 disables the guard
+    pub fn set_max_price_deviation_bps(env: Env, deviation_bps: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_max_price_deviation_bps(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxPriceDeviationBps, &deviation_bps);
+
+        Self::_publish_config_change(
+            &env,
+            "max_price_deviation_bps",
+            old,
+            Self::get_max_price_deviation_bps(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the resolution price deviation guard in basis points (0 if unset)
+    pub fn get_max_price_deviation_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxPriceDeviationBps)
+            .unwrap_or(0)
+    }
+
+    /// Returns the Up/Down dead zone in basis points (0 if unset)
+    pub fn get_price_move_threshold_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PriceMoveThresholdBps)
+            .unwrap_or(0)
+    }
+
+    /// Returns the most recently resolved rounds, oldest first (at most 50)
+    pub fn get_round_history(env: Env) -> Vec<ResolvedRound> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoundHistory)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the outcome code (0 down, 1 up, 2 unchanged, 3 precision) of the round at
+    /// `round_index` in get_round_history, or None if the index is out of range
+    pub fn get_round_outcome(env: Env, round_index: u32) -> Option<u32> {
+        Self::get_round_history(env)
+            .get(round_index)
+            .map(|round| round.outcome)
+    }
+
+    /// Returns the user's most recently resolved bets, oldest first (at most 20)
+    pub fn get_bet_history(env: Env, user: Address) -> Vec<HistoryEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BetHistory(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns user statistics (wins, losses, streaks, volume)
+    pub fn get_user_stats(env: Env, user: Address) -> UserStats {
+        Self::_load_stats(&env, user)
+    }
+
+    /// Clears the caller's stats so testers can re-run scenarios (test mode only)
+    pub fn reset_my_stats(env: Env, user: Address) -> Result<(), ContractError> {
+        user.require_auth();
+
+        if !Self::is_test_mode(env.clone()) {
+            return Err(ContractError::NotInTestMode);
+        }
+
+        env.storage().persistent().remove(&DataKey::UserStats(user));
+
+        Ok(())
+    }
+
+    /// Enables or disables testnet-only helpers (admin only)
+    pub fn set_test_mode(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::is_test_mode(env.clone());
+
+        env.storage().persistent().set(&DataKey::TestMode, &enabled);
+
+        Self::_publish_config_change(&env, "test_mode", old, Self::is_test_mode(env.clone()));
+
+        Ok(())
+    }
+
+    /// Returns true if testnet-only helpers are enabled (false if unset)
+    pub fn is_test_mode(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TestMode)
+            .unwrap_or(false)
+    }
+
+    /// Returns the user's net profit in stroops (total_won - total_wagered)
+    pub fn get_user_roi(env: Env, user: Address) -> i128 {
+        let stats = Self::_load_stats(&env, user);
+        stats.total_won.saturating_sub(stats.total_wagered)
+    }
+
+    /// Returns the top users by best win streak, highest first
+    pub fn get_leaderboard(env: Env) -> Vec<(Address, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Leaderboard)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Sets the win streak milestones that earn a bonus, as streak -> bonus amount (admin only)
+    /// An empty map disables bonuses
+    pub fn set_streak_bonuses(env: Env, bonuses: Map<u32, i128>) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        if bonuses.values().iter().any(|bonus| bonus <= 0) {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let old = Self::get_streak_bonuses(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::StreakBonuses, &bonuses);
+
+        Self::_publish_config_change(
+            &env,
+            "streak_bonuses",
+            old,
+            Self::get_streak_bonuses(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the win streak milestones and their bonuses (empty if none are configured)
+    pub fn get_streak_bonuses(env: Env) -> Map<u32, i128> {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::StreakBonuses)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Returns user's claimable winnings
+    pub fn get_pending_winnings(env: Env, user: Address) -> i128 {
+        let key = DataKey::PendingWinnings(user);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    /// Returns every user with unclaimed winnings; use get_pending_claimants_page for large sets
+    /// Users are kept in slots: a claim moves the last claimant into the freed slot
+    pub fn get_pending_claimants(env: Env) -> Vec<Address> {
+        let count = Self::get_pending_claimant_count(env.clone());
+        Self::get_pending_claimants_page(env, 0, count)
+    }
+
+    /// Returns up to `limit` users with unclaimed winnings starting at slot `start`
+    /// Out-of-range bounds are clamped to the number of claimants
+    pub fn get_pending_claimants_page(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let count = Self::get_pending_claimant_count(env.clone());
+        let start = start.min(count);
+        let end = start.saturating_add(limit).min(count);
+
+        let mut claimants = Vec::new(&env);
+        for slot in start..end {
+            if let Some(user) = env.storage().persistent().get(&ExtDataKey::Claimant(slot)) {
+                claimants.push_back(user);
+            }
+        }
+
+        claimants
+    }
+
+    /// Returns how many users have unclaimed winnings
+    pub fn get_pending_claimant_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::ClaimantCount)
+            .unwrap_or(0)
+    }
+
+    /// Places a bet on the active round (Up/Down mode only)
+    /// min_payout_multiplier: optional floor on the side's payout multiplier after the bet,
+    /// scaled by ODDS_SCALE like get_odds; the bet is rejected if the pools moved below it
+    pub fn place_bet(
+        env: Env,
+        user: Address,
+        amount: i128,
+        side: BetSide,
+        min_payout_multiplier: Option<u64>,
+    ) -> Result<(), ContractError> {
+        user.require_auth();
+
+        let (mut round, mut positions) = Self::_check_bet(&env, &user, amount)?;
+
+        if let Some(min_multiplier) = min_payout_multiplier {
+            let (side_pool, other_pool) = match side {
+                BetSide::Up => (round.pool_up, round.pool_down),
+                BetSide::Down => (round.pool_down, round.pool_up),
+            };
+            let side_pool = side_pool
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            let multiplier = side_pool
+                .checked_add(other_pool)
+                .and_then(|total| total.checked_mul(ODDS_SCALE))
+                .ok_or(ContractError::Overflow)?
+                / side_pool;
+            if multiplier < min_multiplier as i128 {
+                return Err(ContractError::SlippageExceeded);
+            }
+        }
+
+        Self::_take_stake(&env, &user, amount)?;
+        Self::_record_bet_ledger(&env, &user);
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::LastBetSide(user.clone()), &side);
+
+        let position = UserPosition {
+            amount,
+            side: side.clone(),
+        };
+        positions.set(user.clone(), position);
+
+        match side {
+            BetSide::Up => {
+                round.pool_up = round
+                    .pool_up
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+            BetSide::Down => {
+                round.pool_down = round
+                    .pool_down
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+
+        // Also keep legacy Positions storage for backwards compatibility
+        let mut legacy_positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+        legacy_positions.set(user, UserPosition { amount, side });
+        env.storage()
+            .persistent()
+            .set(&DataKey::Positions, &legacy_positions);
+
+        Self::_extend_ttl(&env, &DataKey::ActiveRound);
+        Self::_extend_ttl(&env, &DataKey::UpDownPositions);
+        Self::_extend_ttl(&env, &DataKey::Positions);
+
+        Self::_publish_pool_update(&env, &round);
+
+        Ok(())
+    }
+
+    /// Dry-runs place_bet: returns the error place_bet would fail with, or Ok if it would succeed
+    /// Runs the same checks without requiring auth or changing state; no check depends on the side
+    pub fn validate_bet(
+        env: Env,
+        user: Address,
+        amount: i128,
+        _side: BetSide,
+    ) -> Result<(), ContractError> {
+        Self::_check_bet(&env, &user, amount)?;
+
+        Ok(())
+    }
+
+    /// Runs place_bet's checks, returning the active round and its Up/Down positions
+    fn _check_bet(
+        env: &Env,
+        user: &Address,
+        amount: i128,
+    ) -> Result<(Round, Map<Address, UserPosition>), ContractError> {
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::ContractPaused);
+        }
+
+        Self::_check_bet_cooldown(env, user)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Verify round is in Up/Down mode
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        if !Self::_betting_open(env, &round) {
+            return Err(ContractError::RoundEnded);
+        }
+
+        if Self::_stake_balance(env, user) < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        // Use UpDownPositions storage for Up/Down mode
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
+
+        if positions.contains_key(user.clone()) {
+            return Err(ContractError::AlreadyBet);
+        }
+
+        // Only new entries count against the cap
+        if positions.len() >= Self::get_max_participants(env.clone()) {
+            return Err(ContractError::RoundFull);
+        }
+
+        Ok((round, positions))
+    }
+
+    /// Places an Up/Down bet of `bps` basis points of the user's balance (10000 = all-in)
+    /// Rounds the amount down and returns it; a bet that rounds to 0 is rejected
+    pub fn place_bet_pct(
+        env: Env,
+        user: Address,
+        bps: u32,
+        side: BetSide,
+    ) -> Result<i128, ContractError> {
+        if bps as i128 > BPS_DENOMINATOR {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let amount = Self::_stake_balance(&env, &user)
+            .checked_mul(bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / BPS_DENOMINATOR;
+
+        Self::place_bet(env, user, amount, side, None)?;
+
+        Ok(amount)
+    }
+
+    /// Opts the user in or out of auto-compounding (see _auto_compound)
+    /// At most MAX_AUTO_COMPOUNDERS users can be opted in at once
+    pub fn set_auto_compound(env: Env, user: Address, enabled: bool) -> Result<(), ContractError> {
+        user.require_auth();
+
+        let mut compounders = Self::get_auto_compounders(env.clone());
+        let index = compounders.first_index_of(&user);
+        let key = ExtDataKey::AutoCompound(user.clone());
+        if enabled {
+            if index.is_none() {
+                if compounders.len() >= MAX_AUTO_COMPOUNDERS {
+                    return Err(ContractError::AutoCompoundFull);
+                }
+                compounders.push_back(user);
+            }
+            env.storage().persistent().set(&key, &true);
+            Self::_extend_ttl(&env, &key);
+        } else {
+            if let Some(index) = index {
+                compounders.remove(index);
+            }
+            env.storage().persistent().remove(&key);
+            Self::_consume_compoundable(&env, &user, i128::MAX);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::AutoCompounders, &compounders);
+        Self::_extend_ttl(&env, &ExtDataKey::AutoCompounders);
+
+        Ok(())
+    }
+
+    /// Returns the users opted in to auto-compounding
+    pub fn get_auto_compounders(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::AutoCompounders)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Records Up/Down winnings an opted-in user may have auto-compounded
+    fn _add_compoundable(env: &Env, user: &Address, amount: i128) -> Result<(), ContractError> {
+        if !Self::is_auto_compound(env.clone(), user.clone()) {
+            return Ok(());
+        }
+
+        let key = ExtDataKey::Compoundable(user.clone());
+        let compoundable: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let compoundable = compoundable
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&key, &compoundable);
+        Self::_extend_ttl(env, &key);
+
+        Ok(())
+    }
+
+    /// Takes up to `amount` off the user's compoundable winnings, returning how much was taken
+    fn _consume_compoundable(env: &Env, user: &Address, amount: i128) -> i128 {
+        let key = ExtDataKey::Compoundable(user.clone());
+        let compoundable: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let consumed = compoundable.min(amount);
+        if consumed >= compoundable {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&key, &(compoundable - consumed));
+        }
+        consumed
+    }
+
+    /// Returns true if the user's pending winnings are re-bet each new Up/Down round
+    pub fn is_auto_compound(env: Env, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::AutoCompound(user))
+            .unwrap_or(false)
+    }
+
+    /// Places opted-in users' pending winnings as bets in the just-created Up/Down round
+    /// Only Up/Down winnings earned while opted in are bet (refunds and Precision winnings
+    /// stay pending), on the side of the user's most recent Up/Down bet, without passing
+    /// through their balance. Only the capped opt-in list is walked. Users with no prior
+    /// Up/Down bet are skipped, as is everyone once the round reaches max participants.
+    /// Precision rounds and paused markets never compound. There is no per-bet maximum in
+    /// this contract, so the full pending amount is always used; bet cooldowns do not apply.
+    fn _auto_compound(env: &Env) -> Result<(), ContractError> {
+        let mut round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+        let mut positions = Self::get_updown_positions(env.clone());
+        let mut legacy_positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(env));
+        let max_participants = Self::get_max_participants(env.clone());
+
+        for user in Self::get_auto_compounders(env.clone()).iter() {
+            if positions.len() >= max_participants {
+                break;
+            }
+            if positions.contains_key(user.clone()) {
+                continue;
+            }
+            let side: Option<BetSide> = env
+                .storage()
+                .persistent()
+                .get(&ExtDataKey::LastBetSide(user.clone()));
+            let Some(side) = side else {
+                continue;
+            };
+            let pending = Self::get_pending_winnings(env.clone(), user.clone());
+            let amount = Self::_consume_compoundable(env, &user, pending);
+            if amount <= 0 {
+                continue;
+            }
+
+            // The stake is already held by the contract, so it moves straight into the pool
+            let pending_key = DataKey::PendingWinnings(user.clone());
+            if amount == pending {
+                env.storage().persistent().remove(&pending_key);
+                Self::_remove_pending_claimant(env, &user);
+            } else {
+                env.storage()
+                    .persistent()
+                    .set(&pending_key, &(pending - amount));
+            }
+
+            match side {
+                BetSide::Up => {
+                    round.pool_up = round
+                        .pool_up
+                        .checked_add(amount)
+                        .ok_or(ContractError::Overflow)?;
+                }
+                BetSide::Down => {
+                    round.pool_down = round
+                        .pool_down
+                        .checked_add(amount)
+                        .ok_or(ContractError::Overflow)?;
+                }
+            }
+            let position = UserPosition {
+                amount,
+                side: side.clone(),
+            };
+            positions.set(user.clone(), position.clone());
+            legacy_positions.set(user.clone(), position);
+
+            #[allow(deprecated)]
+            env.events().publish(
+                (symbol_short!("bet"), symbol_short!("compound")),
+                (user, amount, side),
+            );
+        }
+
+        if positions.is_empty() {
+            return Ok(());
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Positions, &legacy_positions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+        Self::_extend_ttl(env, &DataKey::UpDownPositions);
+        Self::_extend_ttl(env, &DataKey::Positions);
+        Self::_extend_ttl(env, &DataKey::ActiveRound);
+
+        Self::_publish_pool_update(env, &round);
+
+        Ok(())
+    }
+
+    /// Rejects a bet placed within the bet cooldown of the user's previous one
+    fn _check_bet_cooldown(env: &Env, user: &Address) -> Result<(), ContractError> {
+        let cooldown = Self::get_bet_cooldown(env.clone());
+        if cooldown == 0 {
+            return Ok(());
+        }
+
+        let last_bet: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastBetLedger(user.clone()));
+        if let Some(last_bet) = last_bet {
+            if env.ledger().sequence() < last_bet.saturating_add(cooldown) {
+                return Err(ContractError::BetCooldownActive);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _record_bet_ledger(env: &Env, user: &Address) {
+        let key = DataKey::LastBetLedger(user.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &env.ledger().sequence());
+        Self::_extend_ttl(env, &key);
+    }
+
+    /// Publishes the Up/Down pool totals so indexers can track live odds
+    fn _publish_pool_update(env: &Env, round: &Round) {
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("pool"), symbol_short!("update")),
+            (round.pool_up, round.pool_down),
+        );
+    }
+
+    /// Places a precision prediction on the active round (Precision/Legends mode only)
+    /// predicted_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
+    pub fn place_precision_prediction(
+        env: Env,
+        user: Address,
+        amount: i128,
+        predicted_price: u128,
+    ) -> Result<(), ContractError> {
+        user.require_auth();
+
+        if Self::is_paused(env.clone()) {
+            return Err(ContractError::ContractPaused);
+        }
+
+        Self::_check_bet_cooldown(&env, &user)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        // Validate price scale (must be 4 decimal places, max 9999.9999 XLM)
+        if predicted_price > MAX_PRECISION_PRICE {
+            return Err(ContractError::InvalidPriceScale);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Verify round is in a Precision mode
+        if round.mode == RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        if !Self::_betting_open(&env, &round) {
+            return Err(ContractError::RoundEnded);
+        }
+
+        if Self::_stake_balance(&env, &user) < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        // Check if user already has a prediction in this round
+        let mut index = Self::_precision_index(&env);
+        if index.contains_key(user.clone()) {
+            return Err(ContractError::AlreadyBet);
+        }
+
+        let mut predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(&env));
+
+        if predictions.len() >= Self::get_max_predictions(env.clone()) {
+            return Err(ContractError::RoundFull);
+        }
+
+        // Deduct balance
+        Self::_take_stake(&env, &user, amount)?;
+        Self::_record_bet_ledger(&env, &user);
+
+        // Store prediction
+        let prediction = PrecisionPrediction {
+            user: user.clone(),
+            predicted_price,
+            amount,
+        };
+        index.set(user.clone(), predictions.len());
+        predictions.push_back(prediction);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrecisionPositions, &predictions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrecisionIndex, &index);
+        Self::_extend_ttl(&env, &DataKey::ActiveRound);
+        Self::_extend_ttl(&env, &DataKey::PrecisionPositions);
+        Self::_extend_ttl(&env, &DataKey::PrecisionIndex);
+
+        // Emit event for precision prediction
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("predict"), symbol_short!("price")),
+            (user, predicted_price, round.start_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Alias for place_precision_prediction - allows users to submit exact price predictions
+    /// guessed_price: price scaled to 4 decimals (e.g., 0.2297 → 2297)
+    pub fn predict_price(
+        env: Env,
+        user: Address,
+        guessed_price: u128,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        Self::place_precision_prediction(env, user, amount, guessed_price)
+    }
+
+    /// Places a prediction on the active round, dispatching on its mode
+    /// Up/Down rounds take `side`; Precision rounds take `predicted_price`
+    pub fn place_prediction(
+        env: Env,
+        user: Address,
+        amount: i128,
+        side: Option<BetSide>,
+        predicted_price: Option<u128>,
+    ) -> Result<(), ContractError> {
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        match (round.mode, side, predicted_price) {
+            (RoundMode::UpDown, Some(side), None) => Self::place_bet(env, user, amount, side, None),
+            (RoundMode::Precision | RoundMode::PrecisionWeighted, None, Some(price)) => {
+                Self::place_precision_prediction(env, user, amount, price)
+            }
+            _ => Err(ContractError::WrongModeForPrediction),
+        }
+    }
+
+    /// Withdraws the user's Up/Down bet while the betting window is still open
+    /// Refunds the staked amount to the user's balance and returns it
+    pub fn withdraw_bet(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        Self::_reduce_position(&env, user, None)
+    }
+
+    /// Lowers the user's Up/Down stake by `amount` while the betting window is still open
+    /// Refunds `amount` to the user's balance and returns it; a stake reduced to zero is removed
+    pub fn reduce_bet(env: Env, user: Address, amount: i128) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        Self::_reduce_position(&env, user, Some(amount))
+    }
+
+    /// Takes `amount` (or the whole stake if None) out of the user's Up/Down position and pool
+    fn _reduce_position(
+        env: &Env,
+        user: Address,
+        amount: Option<i128>,
+    ) -> Result<i128, ContractError> {
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        if !Self::_betting_open(env, &round) {
+            return Err(ContractError::RoundEnded);
+        }
+
+        Self::_take_from_position(env, round, user, amount)
+    }
+
+    /// Removes `amount` (or the whole stake if None) from the user's Up/Down position and
+    /// its pool in `round`, refunding it to the user; callers enforce the betting window
+    fn _take_from_position(
+        env: &Env,
+        mut round: Round,
+        user: Address,
+        amount: Option<i128>,
+    ) -> Result<i128, ContractError> {
+        let mut positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
+
+        let mut position = positions
+            .get(user.clone())
+            .ok_or(ContractError::NoPosition)?;
+
+        let amount = amount.unwrap_or(position.amount);
+        if amount > position.amount {
+            return Err(ContractError::AmountExceedsStake);
+        }
+
+        match position.side {
+            BetSide::Up => {
+                round.pool_up = round
+                    .pool_up
+                    .checked_sub(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+            BetSide::Down => {
+                round.pool_down = round
+                    .pool_down
+                    .checked_sub(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        Self::_pay_out(env, &user, amount)?;
+
+        // Keep legacy Positions storage in sync
+        let mut legacy_positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(env));
+
+        position.amount -= amount;
+        if position.amount == 0 {
+            positions.remove(user.clone());
+            legacy_positions.remove(user);
+        } else {
+            positions.set(user.clone(), position.clone());
+            legacy_positions.set(user, position);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpDownPositions, &positions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveRound, &round);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Positions, &legacy_positions);
+
+        Self::_publish_pool_update(env, &round);
+
+        Ok(amount)
+    }
+
+    /// Withdraws the user's precision prediction while the betting window is still open
+    /// Refunds the staked amount to the user's balance and returns it
+    pub fn withdraw_precision_prediction(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        if round.mode == RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        if !Self::_betting_open(&env, &round) {
+            return Err(ContractError::RoundEnded);
+        }
+
+        Self::_remove_precision_prediction(&env, user)
+    }
+
+    /// Removes the user's precision prediction and refunds its stake to the user
+    /// Callers enforce the betting window
+    fn _remove_precision_prediction(env: &Env, user: Address) -> Result<i128, ContractError> {
+        let mut predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(env));
+
+        let mut index = Self::_precision_index(env);
+        let position = index.get(user.clone()).ok_or(ContractError::NoPosition)?;
+        let prediction = predictions.get(position).ok_or(ContractError::NoPosition)?;
+
+        Self::_pay_out(env, &user, prediction.amount)?;
+
+        // Keep resolution order stable; later predictions shift down by one
+        predictions.remove(position);
+        index.remove(user);
+        for i in position..predictions.len() {
+            if let Some(pred) = predictions.get(i) {
+                index.set(pred.user, i);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrecisionPositions, &predictions);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrecisionIndex, &index);
+
+        Ok(prediction.amount)
+    }
+
+    /// Returns the total stake locked in the active round (0 when there is none)
+    pub fn get_tvl(env: Env) -> i128 {
+        let round: Option<Round> = env.storage().persistent().get(&DataKey::ActiveRound);
+        match round {
+            Some(round) if round.mode == RoundMode::UpDown => {
+                round.pool_up.saturating_add(round.pool_down)
+            }
+            Some(_) => Self::get_precision_predictions(env)
+                .iter()
+                .fold(0i128, |total, pred| total.saturating_add(pred.amount)),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of distinct participants in the active round (0 if none)
+    pub fn get_participant_count(env: Env) -> u32 {
+        if !env.storage().persistent().has(&DataKey::ActiveRound) {
+            return 0;
+        }
+
+        Self::_participant_count(&env)
+    }
+
+    /// Returns the current (up, down) payout multipliers scaled by ODDS_SCALE
+    /// multiplier = (pool_up + pool_down) * ODDS_SCALE / side_pool; 0 for an empty side
+    /// None when there is no active round or the round is in Precision mode
+    pub fn get_odds(env: Env) -> Option<(i128, i128)> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        if round.mode != RoundMode::UpDown {
+            return None;
+        }
+
+        let total_scaled = round
+            .pool_up
+            .checked_add(round.pool_down)?
+            .checked_mul(ODDS_SCALE)?;
+        let odds = |pool: i128| {
+            if pool == 0 {
+                Some(0)
+            } else {
+                total_scaled.checked_div(pool)
+            }
+        };
+
+        Some((odds(round.pool_up)?, odds(round.pool_down)?))
+    }
+
+    /// Returns the pool-implied probability that the price goes up, in basis points
+    /// probability = pool_up * 10_000 / (pool_up + pool_down)
+    /// None when there is no active round, the round is in Precision mode, or both pools are empty
+    pub fn get_implied_probability(env: Env) -> Option<u32> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        if round.mode != RoundMode::UpDown {
+            return None;
+        }
+
+        let total = round.pool_up.checked_add(round.pool_down)?;
+        if total <= 0 {
+            return None;
+        }
+
+        let bps = round.pool_up.checked_mul(BPS_DENOMINATOR)? / total;
+        u32::try_from(bps).ok()
+    }
+
+    /// Returns (pool_up, pool_down, projected_fee) for the active Up/Down round
+    /// The losing side is unknown before resolution, so projected_fee is the fee on the
+    /// smaller pool: a conservative estimate of what the house will take
+    /// None when there is no active round or the round is in Precision mode
+    pub fn get_net_pools(env: Env) -> Option<(i128, i128, i128)> {
+        let round: Round = env.storage().persistent().get(&DataKey::ActiveRound)?;
+        if round.mode != RoundMode::UpDown {
+            return None;
+        }
+
+        let smaller_pool = round.pool_up.min(round.pool_down);
+        let projected_fee = Self::_fee_on(&env, smaller_pool).ok()?;
+
+        Some((round.pool_up, round.pool_down, projected_fee))
+    }
+
+    /// Estimates the payout for a hypothetical bet if `side` wins (read-only)
+    /// payout = amount + amount * (opposing_pool - fee - reward) / (chosen_pool + amount)
+    /// The reward is always deducted, so rounds settled without one pay at least this much
+    pub fn estimate_payout(env: Env, side: BetSide, amount: i128) -> Result<i128, ContractError> {
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        if round.mode != RoundMode::UpDown {
+            return Err(ContractError::WrongModeForPrediction);
+        }
+
+        let (chosen_pool, opposing_pool) = match side {
+            BetSide::Up => (round.pool_up, round.pool_down),
+            BetSide::Down => (round.pool_down, round.pool_up),
+        };
+
+        let opposing_pool = Self::_net_losing_pool(&env, opposing_pool)?;
+        let chosen_pool = chosen_pool
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        let share = amount
+            .checked_mul(opposing_pool)
+            .ok_or(ContractError::Overflow)?
+            / chosen_pool;
+
+        amount.checked_add(share).ok_or(ContractError::Overflow)
+    }
+
+    /// Returns what the user would collect if their position won with the current pools
+    /// Up/Down: amount + amount * (opposing_pool - fee - reward) / own_pool, a lower bound
+    /// when the round is settled by a path that pays no resolver reward
+    /// Precision: their stake while they are the closest guess to the start price, else 0
+    /// Returns 0 when there is no active round or the user has no position
+    pub fn get_projected_payout(env: Env, user: Address) -> i128 {
+        let round: Round = match env.storage().persistent().get(&DataKey::ActiveRound) {
+            Some(round) => round,
+            None => return 0,
+        };
+
+        match round.mode {
+            RoundMode::UpDown => Self::_projected_updown_payout(&env, &round, user).unwrap_or(0),
+            RoundMode::Precision | RoundMode::PrecisionWeighted => {
+                Self::_projected_precision_payout(&env, &round, user)
+            }
+        }
+    }
+
+    /// Up/Down branch of get_projected_payout
+    fn _projected_updown_payout(
+        env: &Env,
+        round: &Round,
+        user: Address,
+    ) -> Result<i128, ContractError> {
+        let position = match Self::get_user_position(env.clone(), user) {
+            Some(position) => position,
+            None => return Ok(0),
+        };
+
+        let (own_pool, opposing_pool) = match position.side {
+            BetSide::Up => (round.pool_up, round.pool_down),
+            BetSide::Down => (round.pool_down, round.pool_up),
+        };
+        if own_pool <= 0 {
+            return Ok(0);
+        }
+
+        let opposing_pool = Self::_net_losing_pool(env, opposing_pool)?;
+        let share = position
+            .amount
+            .checked_mul(opposing_pool)
+            .ok_or(ContractError::Overflow)?
+            / own_pool;
+
+        position
+            .amount
+            .checked_add(share)
+            .ok_or(ContractError::Overflow)
+    }
+
+    /// Precision branch of get_projected_payout
+    fn _projected_precision_payout(env: &Env, round: &Round, user: Address) -> i128 {
+        let prediction = match Self::get_user_precision_prediction(env.clone(), user) {
+            Some(prediction) => prediction,
+            None => return 0,
+        };
+
+        let closest = Self::get_precision_predictions(env.clone())
+            .iter()
+            .map(|pred| pred.predicted_price.abs_diff(round.price_start))
+            .min();
+
+        if closest == Some(prediction.predicted_price.abs_diff(round.price_start)) {
+            prediction.amount
+        } else {
+            0
+        }
+    }
+
+    /// Returns user's position in the current round (Up/Down mode)
+    pub fn get_user_position(env: Env, user: Address) -> Option<UserPosition> {
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(&env));
+
+        positions.get(user)
+    }
+
+    /// Returns user's precision prediction in the current round (Precision mode)
+    pub fn get_user_precision_prediction(env: Env, user: Address) -> Option<PrecisionPrediction> {
+        let position = Self::_precision_index(&env).get(user)?;
+        let predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)?;
+
+        predictions.get(position)
+    }
+
+    /// Returns the user's (rank, total) among current precision predictions
+    /// Rank is 1-based by distance to `reference_price`; equal distances share a rank
+    pub fn get_precision_rank(
+        env: Env,
+        user: Address,
+        reference_price: u128,
+    ) -> Option<(u32, u32)> {
+        let prediction = Self::get_user_precision_prediction(env.clone(), user)?;
+        let distance = prediction.predicted_price.abs_diff(reference_price);
+
+        let predictions = Self::get_precision_predictions(env);
+        let closer = predictions
+            .iter()
+            .filter(|pred| pred.predicted_price.abs_diff(reference_price) < distance)
+            .count() as u32;
+
+        Some((closer + 1, predictions.len()))
+    }
+
+    /// Returns the user's balance, pending winnings, active-round positions and stats together
+    /// The balance is the stake token balance when one is configured, vXLM otherwise
+    pub fn get_portfolio(env: Env, user: Address) -> Portfolio {
+        let mut updown_position = Vec::new(&env);
+        if let Some(position) = Self::get_user_position(env.clone(), user.clone()) {
+            updown_position.push_back(position);
+        }
+        let mut precision_prediction = Vec::new(&env);
+        if let Some(prediction) = Self::get_user_precision_prediction(env.clone(), user.clone()) {
+            precision_prediction.push_back(prediction);
+        }
+
+        Portfolio {
+            balance: Self::_stake_balance(&env, &user),
+            pending_winnings: Self::get_pending_winnings(env.clone(), user.clone()),
+            updown_position,
+            precision_prediction,
+            stats: Self::get_user_stats(env, user),
+        }
+    }
+
+    /// Returns the user -> PrecisionPositions index for the active round
+    fn _precision_index(env: &Env) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PrecisionIndex)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Returns all precision predictions for the current round
+    pub fn get_precision_predictions(env: Env) -> Vec<PrecisionPrediction> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns up to `limit` precision predictions starting at `start`
+    /// Out-of-range bounds are clamped to the number of stored predictions
+    pub fn get_precision_predictions_page(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Vec<PrecisionPrediction> {
+        let predictions = Self::get_precision_predictions(env);
+        let len = predictions.len();
+        let start = start.min(len);
+        let end = start.saturating_add(limit).min(len);
+
+        predictions.slice(start..end)
+    }
+
+    /// Returns the number of precision predictions in the current round
+    pub fn get_precision_prediction_count(env: Env) -> u32 {
+        Self::get_precision_predictions(env).len()
+    }
+
+    /// Returns (current, max) precision predictions for the current round
+    pub fn get_prediction_capacity(env: Env) -> (u32, u32) {
+        (
+            Self::get_precision_prediction_count(env.clone()),
+            Self::get_max_predictions(env),
+        )
+    }
+
+    /// Returns all Up/Down positions for the current round
+    pub fn get_updown_positions(env: Env) -> Map<Address, UserPosition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Returns the active round's Up/Down positions as (user, position) pairs
+    /// Same data as get_updown_positions, ordered by address for deterministic iteration
+    pub fn get_updown_positions_list(env: Env) -> Vec<(Address, UserPosition)> {
+        let mut list = Vec::new(&env);
+        for entry in Self::get_updown_positions(env.clone()).iter() {
+            list.push_back(entry);
+        }
+        list
+    }
+
+    /// Moves entries from the legacy Positions map into UpDownPositions (admin only)
+    /// Existing UpDownPositions entries are newer and are never overwritten; the legacy key
+    /// is removed afterwards. Returns the number of entries migrated
+    pub fn migrate_positions(env: Env) -> Result<u32, ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let legacy_positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+        let mut positions = Self::get_updown_positions(env.clone());
+
+        let mut migrated: u32 = 0;
+        for (user, position) in legacy_positions.iter() {
+            if !positions.contains_key(user.clone()) {
+                positions.set(user, position);
+                migrated += 1;
+            }
+        }
+
+        if migrated > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::UpDownPositions, &positions);
+            Self::_extend_ttl(&env, &DataKey::UpDownPositions);
+        }
+        env.storage().persistent().remove(&DataKey::Positions);
+
+        Ok(migrated)
+    }
+
+    /// Resolves the round with final price (oracle only)
+    /// Mode 0 (Up/Down): Winners split losers' pool proportionally; ties get refunds
+    /// Mode 1 (Precision/Legends): Closest guess wins full pot; ties split evenly,
+    /// go to the earliest tied guess under TieBreakMode::Earliest, or to one stake-weighted
+    /// pseudo-random tied guess under TieBreakMode::Random
+    /// (exact matches are the closest possible, so several exact matches split it among themselves)
+    /// Mode 2 (Precision weighted): Pot split by 1 / (distance + 1) across all guesses
+    pub fn resolve_round(
+        env: Env,
+        payload: crate::types::OraclePayload,
+    ) -> Result<(), ContractError> {
+        let (oracle, round) = Self::_validate_oracle_payload(&env, &payload)?;
+
+        Self::_settle_round(&env, &round, payload.price, Some(oracle))
+    }
+
+    /// Authorizes the oracle and checks the payload against the active round:
+    /// price, round ID, freshness, round end and resolution window
+    /// Returns the oracle and the round to settle
+    fn _validate_oracle_payload(
+        env: &Env,
+        payload: &crate::types::OraclePayload,
+    ) -> Result<(Address, Round), ContractError> {
+        if payload.price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        // Verify round ID matches to prevent cross-round replays
+        if payload.round_id != round.start_ledger {
+            return Err(ContractError::InvalidOracleRound);
+        }
+
+        // Verify data freshness (max 300 seconds / 5 minutes old)
+        let current_time = env.ledger().timestamp();
+        if current_time > payload.timestamp + 300 {
+            return Err(ContractError::StaleOracleData);
+        }
+
+        // Verify round has reached end_ledger
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        // Verify the oracle is still within the allowed resolution delay
+        if Self::_resolution_window_expired(env, &round) {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        Self::_check_direct_settlement(env)?;
+
+        Ok((oracle, round))
+    }
+
+    /// Rejects settling without review while a review window is configured or a
+    /// resolution is pending; such rounds settle only via confirm_resolution
+    fn _check_direct_settlement(env: &Env) -> Result<(), ContractError> {
+        if Self::get_review_window(env.clone()) > 0
+            || Self::get_pending_resolution(env.clone()).is_some()
+        {
+            return Err(ContractError::ReviewWindowActive);
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the round exactly like resolve_round (oracle only), but credits this
+    /// round's payouts straight to balances instead of pending winnings, so participants
+    /// need not claim. Winnings left unclaimed from earlier rounds stay pending
+    pub fn resolve_and_pay(
+        env: Env,
+        payload: crate::types::OraclePayload,
+    ) -> Result<(), ContractError> {
+        let (oracle, round) = Self::_validate_oracle_payload(&env, &payload)?;
+
+        // Participants and their prior pending must be captured before settlement
+        let mut recipients = Self::_participants(&env);
+        if !recipients.contains(&oracle) {
+            recipients.push_back(oracle.clone());
+        }
+        let mut pending_before: Vec<i128> = Vec::new(&env);
+        for user in recipients.iter() {
+            pending_before.push_back(Self::get_pending_winnings(env.clone(), user));
+        }
+
+        Self::_settle_round(&env, &round, payload.price, Some(oracle))?;
+
+        for (user, before) in recipients.iter().zip(pending_before.iter()) {
+            let credited = Self::get_pending_winnings(env.clone(), user.clone()) - before;
+            if credited > 0 {
+                Self::_pay_pending_amount(&env, &user, credited)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Announces the final price for the active round (oracle only)
+    /// The price stays under review for the review window, after which the oracle has the
+    /// expiry grace to confirm it with confirm_resolution
+    pub fn announce_resolution(env: Env, final_price: u128) -> Result<(), ContractError> {
+        if final_price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        if Self::_resolution_window_expired(&env, &round) {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        if Self::get_pending_resolution(env.clone()).is_some() {
+            return Err(ContractError::ResolutionAlreadyAnnounced);
+        }
+
+        let confirm_ledger = current_ledger.saturating_add(Self::get_review_window(env.clone()));
+        let pending = PendingResolution {
+            price: final_price,
+            announced_ledger: current_ledger,
+            confirm_ledger,
+            deadline_ledger: confirm_ledger.saturating_add(Self::get_expiry_grace(env.clone())),
+        };
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::PendingResolution, &pending);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("announced")),
+            (final_price, confirm_ledger, pending.deadline_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Settles the active round at the announced price once the review window has passed
+    /// (oracle only); must happen by the pending resolution's deadline_ledger
+    pub fn confirm_resolution(env: Env) -> Result<(), ContractError> {
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Oracle)
+            .ok_or(ContractError::OracleNotSet)?;
+
+        oracle.require_auth();
+
+        let pending = Self::get_pending_resolution(env.clone())
+            .ok_or(ContractError::ResolutionNotAnnounced)?;
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < pending.confirm_ledger {
+            return Err(ContractError::ReviewWindowActive);
+        }
+        if current_ledger > pending.deadline_ledger {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        Self::_settle_round(&env, &round, pending.price, Some(oracle))
+    }
+
+    /// Refunds the active round when an announced resolution was not confirmed in time
+    /// Callable by anyone once current_ledger > the pending resolution's deadline_ledger;
+    /// the refund also clears the pending resolution
+    pub fn cancel_resolution(env: Env) -> Result<(), ContractError> {
+        Self::get_pending_resolution(env.clone()).ok_or(ContractError::ResolutionNotAnnounced)?;
+
+        Self::_refund_expired_round(&env)
+    }
+
+    /// Returns the resolution announced for the active round, if any
+    pub fn get_pending_resolution(env: Env) -> Option<PendingResolution> {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::PendingResolution)
+    }
+
+    /// Adds an oracle to the multi-oracle set (admin only)
+    pub fn add_oracle(env: Env, oracle: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_oracles(env.clone());
+
+        let mut oracles = Self::get_oracles(env.clone());
+        if !oracles.contains(&oracle) {
+            oracles.push_back(oracle);
+            env.storage().persistent().set(&DataKey::Oracles, &oracles);
+        }
+
+        Self::_publish_config_change(&env, "oracles", old, Self::get_oracles(env.clone()));
+
+        Ok(())
+    }
+
+    /// Removes an oracle from the multi-oracle set (admin only)
+    pub fn remove_oracle(env: Env, oracle: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_oracles(env.clone());
+
+        let mut oracles = Self::get_oracles(env.clone());
+        if let Some(index) = oracles.first_index_of(&oracle) {
+            oracles.remove(index);
+            env.storage().persistent().set(&DataKey::Oracles, &oracles);
+        }
+
+        Self::_publish_config_change(&env, "oracles", old, Self::get_oracles(env.clone()));
+
+        Ok(())
+    }
+
+    /// Returns the multi-oracle set
+    pub fn get_oracles(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Oracles)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Sets the minimum number of price submissions required to finalize (admin only)
+    pub fn set_oracle_quorum(env: Env, quorum: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_oracle_quorum(env.clone());
+
+        if quorum == 0 {
+            return Err(ContractError::InvalidQuorum);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::OracleQuorum, &quorum);
+
+        Self::_publish_config_change(
+            &env,
+            "oracle_quorum",
+            old,
+            Self::get_oracle_quorum(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the oracle quorum (defaults to 1)
+    pub fn get_oracle_quorum(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OracleQuorum)
+            .unwrap_or(1)
+    }
+
+    /// Submits a final price for the active round (registered oracles only)
+    /// Submissions open once the round reaches end_ledger and close with the resolution window;
+    /// resubmitting overwrites
+    pub fn submit_price(env: Env, oracle: Address, price: u128) -> Result<(), ContractError> {
+        oracle.require_auth();
+
+        if price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        if !Self::get_oracles(env.clone()).contains(&oracle) {
+            return Err(ContractError::UnauthorizedOracle);
+        }
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        if Self::_resolution_window_expired(&env, &round) {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        let mut submissions = Self::get_price_submissions(env.clone());
+        submissions.set(oracle.clone(), price);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriceSubmissions, &submissions);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("oracle"), symbol_short!("submit")),
+            (oracle, price, round.start_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the price submissions for the active round
+    pub fn get_price_submissions(env: Env) -> Map<Address, u128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PriceSubmissions)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Resolves the active round with the median of submitted oracle prices
+    /// Callable by anyone once the quorum of submissions is reached; only oracles still in
+    /// the oracle set count towards the quorum and the median
+    pub fn finalize_resolution(env: Env) -> Result<(), ContractError> {
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        if Self::_resolution_window_expired(&env, &round) {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        Self::_check_direct_settlement(&env)?;
+
+        let oracles = Self::get_oracles(env.clone());
+        let mut prices = Vec::new(&env);
+        for (oracle, price) in Self::get_price_submissions(env.clone()).iter() {
+            if oracles.contains(&oracle) {
+                prices.push_back(price);
+            }
+        }
+        if prices.is_empty() || prices.len() < Self::get_oracle_quorum(env.clone()) {
+            return Err(ContractError::InsufficientOraclePrices);
+        }
+
+        let median = Self::_median(&env, prices)?;
+        Self::_settle_round(&env, &round, median, None)
+    }
+
+    /// Sets the oracle contract rounds pull their final price from (admin only)
+    pub fn set_oracle_contract(env: Env, oracle_contract: Address) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_oracle_contract(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::OracleContract, &oracle_contract);
+
+        Self::_publish_config_change(
+            &env,
+            "oracle_contract",
+            old,
+            Self::get_oracle_contract(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the oracle contract rounds pull their final price from, if set
+    pub fn get_oracle_contract(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::OracleContract)
+    }
+
+    /// Resolves the active round with the price read from the oracle contract
+    /// Callable by anyone once the round has ended, since the price comes from a trusted source
+    pub fn resolve_from_oracle_contract(env: Env) -> Result<(), ContractError> {
+        let oracle_contract =
+            Self::get_oracle_contract(env.clone()).ok_or(ContractError::OracleNotSet)?;
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        if Self::_resolution_window_expired(&env, &round) {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        Self::_check_direct_settlement(&env)?;
+
+        let price = PriceOracleClient::new(&env, &oracle_contract).get_price(&ORACLE_ASSET);
+        if price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        Self::_settle_round(&env, &round, price, None)
+    }
+
+    /// Sets the Ed25519 public key the oracle signs resolution prices with (admin only)
+    pub fn set_oracle_signing_key(env: Env, key: BytesN<32>) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_oracle_signing_key(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::OracleSigningKey, &key);
+
+        Self::_publish_config_change(
+            &env,
+            "oracle_signing_key",
+            old,
+            Self::get_oracle_signing_key(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the oracle's Ed25519 signing key, if set
+    pub fn get_oracle_signing_key(env: Env) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&ExtDataKey::OracleSigningKey)
+    }
+
+    /// Resolves the active round with a price signed by the oracle's signing key
+    /// Callable by anyone (e.g. a keeper) once the round has ended, since the signature
+    /// proves the price came from the oracle. The signed message is _resolution_message
+    /// Malformed signatures are rejected with InvalidSignature; a well-formed signature that
+    /// doesn't verify makes the host's ed25519_verify abort the call, leaving the round open
+    pub fn resolve_with_signature(
+        env: Env,
+        final_price: u128,
+        oracle_sig: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        if final_price == 0 {
+            return Err(ContractError::InvalidPrice);
+        }
+
+        let signing_key =
+            Self::get_oracle_signing_key(env.clone()).ok_or(ContractError::OracleNotSet)?;
+
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let current_ledger = env.ledger().sequence();
+        if current_ledger < round.end_ledger {
+            return Err(ContractError::RoundNotEnded);
+        }
+
+        if Self::_resolution_window_expired(&env, &round) {
+            return Err(ContractError::ResolutionWindowExpired);
+        }
+
+        Self::_check_direct_settlement(&env)?;
+
+        Self::_check_signature_encoding(&oracle_sig)?;
+        let message = Self::_resolution_message(&env, &round, final_price);
+        env.crypto()
+            .ed25519_verify(&signing_key, &message, &oracle_sig);
+
+        Self::_settle_round(&env, &round, final_price, None)
+    }
+
+    /// Message the oracle signs for resolve_with_signature:
+    /// contract address XDR || round.start_ledger (u32 BE) || final_price (u128 BE)
+    /// The contract address and start ledger stop a signature being replayed on
+    /// another deployment or round
+    pub(crate) fn _resolution_message(env: &Env, round: &Round, final_price: u128) -> Bytes {
+        let mut message = env.current_contract_address().to_xdr(env);
+        message.extend_from_array(&round.start_ledger.to_be_bytes());
+        message.extend_from_array(&final_price.to_be_bytes());
+        message
+    }
+
+    /// Rejects a signature whose S half is not a canonical scalar (S < L), which
+    /// ed25519_verify would otherwise trap on
+    fn _check_signature_encoding(signature: &BytesN<64>) -> Result<(), ContractError> {
+        let signature = signature.to_array();
+        // Compare S against L from the most significant byte down
+        for i in (0..32).rev() {
+            let byte = signature[32 + i];
+            if byte < ED25519_ORDER[i] {
+                return Ok(());
+            }
+            if byte > ED25519_ORDER[i] {
+                break;
+            }
+        }
+
+        Err(ContractError::InvalidSignature)
+    }
+
+    /// Returns the median of a non-empty price list
+    /// Even-sized lists average the two middle values (rounded down)
+    fn _median(env: &Env, prices: Vec<u128>) -> Result<u128, ContractError> {
+        // Insertion sort - the oracle set is small
+        let mut sorted: Vec<u128> = Vec::new(env);
+        for price in prices.iter() {
+            let mut index = sorted.len();
+            while index > 0 && sorted.get(index - 1).unwrap_or(0) > price {
+                index -= 1;
+            }
+            sorted.insert(index, price);
+        }
+
+        let len = sorted.len();
+        let mid = len / 2;
+        let upper = sorted
+            .get(mid)
+            .ok_or(ContractError::InsufficientOraclePrices)?;
+        if len % 2 == 1 {
+            return Ok(upper);
+        }
+
+        let lower = sorted
+            .get(mid - 1)
+            .ok_or(ContractError::InsufficientOraclePrices)?;
+        Ok(lower + (upper - lower) / 2)
+    }
+
+    /// Pays out the active round at `final_price` and clears round storage
+    /// `resolver` is the authorized oracle owed the resolver reward, if any
+    fn _settle_round(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+        resolver: Option<Address>,
+    ) -> Result<(), ContractError> {
+        // Precision rounds are settled on the same 4-decimal scale as their predictions
+        if round.mode != RoundMode::UpDown && final_price > MAX_PRECISION_PRICE {
+            return Err(ContractError::InvalidPriceScale);
+        }
+
+        // Guard against an absurd price from a faulty or rogue oracle
+        let max_deviation_bps = Self::get_max_price_deviation_bps(env.clone());
+        if max_deviation_bps > 0 {
+            let max_deviation = round
+                .price_start
+                .checked_mul(max_deviation_bps as u128)
+                .ok_or(ContractError::Overflow)?
+                / BPS_DENOMINATOR as u128;
+            if final_price.abs_diff(round.price_start) > max_deviation {
+                return Err(ContractError::PriceDeviationTooLarge);
+            }
+        }
+
+        let bets = Self::_snapshot_bets(env, round);
+
+        let participants = Self::_participant_count(env);
+        if participants == 0 {
+            // Nobody bet: there is nothing to pay out, refund, or record in stats
+        } else if participants < Self::get_min_participants(env.clone()) {
+            // Under-subscribed rounds are refunded without touching stats
+            Self::_refund_positions(env)?;
+        } else {
+            Self::_record_rounds_played(env);
+
+            // Branch based on round mode
+            match round.mode {
+                RoundMode::UpDown => {
+                    Self::_resolve_updown_mode(env, round, final_price, resolver)?;
+                }
+                RoundMode::Precision => {
+                    Self::_resolve_precision_mode(env, final_price)?;
+                }
+                RoundMode::PrecisionWeighted => {
+                    Self::_resolve_precision_weighted_mode(env, final_price)?;
+                }
+            }
+        }
+
+        let unchanged = Self::_within_dead_zone(env, round, final_price)?;
+        let outcome = match round.mode {
+            RoundMode::Precision | RoundMode::PrecisionWeighted => OUTCOME_PRECISION,
+            RoundMode::UpDown if unchanged => OUTCOME_UNCHANGED,
+            RoundMode::UpDown if final_price > round.price_start => OUTCOME_UP,
+            RoundMode::UpDown if final_price < round.price_start => OUTCOME_DOWN,
+            RoundMode::UpDown => OUTCOME_UNCHANGED,
+        };
+
+        // Record when the round was resolved for auditing
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastResolvedLedger, &env.ledger().sequence());
+        Self::_record_history(env, round, final_price, outcome);
+        Self::_record_bet_history(env, bets);
+
+        // Emit resolution event before the round (and its pools) is cleared
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("round"), symbol_short!("resolved")),
+            (
+                final_price,
+                round.price_start,
+                outcome,
+                round.pool_up,
+                round.pool_down,
+            ),
+        );
+
+        // Clean up storage
+        Self::_clear_round(env);
+
+        Ok(())
+    }
+
+    /// Appends the round to the history, evicting the oldest entry beyond ROUND_HISTORY_SIZE
+    fn _record_history(env: &Env, round: &Round, final_price: u128, outcome: u32) {
+        let mut history = Self::get_round_history(env.clone());
+        history.push_back(ResolvedRound {
+            price_start: round.price_start,
+            final_price,
+            pool_up: round.pool_up,
+            pool_down: round.pool_down,
+            mode: round.mode.clone(),
+            resolved_ledger: env.ledger().sequence(),
+            outcome,
+        });
+        while history.len() > ROUND_HISTORY_SIZE {
+            history.pop_front();
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoundHistory, &history);
+        Self::_extend_ttl(env, &DataKey::RoundHistory);
+    }
+
+    /// Captures each position of the active round with the user's pending winnings
+    /// before settlement, so the payout can be read off afterwards
+    fn _snapshot_bets(env: &Env, round: &Round) -> Vec<(Address, HistoryEntry, i128)> {
+        let mut bets = Vec::new(env);
+        let mut push = |user: Address, side_or_predicted: u128, amount: i128| {
+            let pending = Self::get_pending_winnings(env.clone(), user.clone());
+            let entry = HistoryEntry {
+                round_start_price: round.price_start,
+                side_or_predicted,
+                amount,
+                payout: 0,
+                resolved_ledger: env.ledger().sequence(),
+            };
+            bets.push_back((user, entry, pending));
+        };
+
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
+        for (user, position) in positions.iter() {
+            let side = match position.side {
+                BetSide::Up => OUTCOME_UP,
+                BetSide::Down => OUTCOME_DOWN,
+            };
+            push(user, side as u128, position.amount);
+        }
+
+        let predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(env));
+        for pred in predictions.iter() {
+            push(pred.user, pred.predicted_price, pred.amount);
+        }
+
+        bets
+    }
+
+    /// Appends settled bets to each user's history, evicting the oldest beyond BET_HISTORY_SIZE
+    fn _record_bet_history(env: &Env, bets: Vec<(Address, HistoryEntry, i128)>) {
+        let mut results: Map<Address, bool> = Map::new(env);
+        for (user, mut entry, pending_before) in bets.iter() {
+            let pending = Self::get_pending_winnings(env.clone(), user.clone());
+            entry.payout = pending.saturating_sub(pending_before);
+            results.set(user.clone(), entry.payout > entry.amount);
+
+            let key = DataKey::BetHistory(user.clone());
+            let mut history = Self::get_bet_history(env.clone(), user);
+            history.push_back(entry);
+            while history.len() > BET_HISTORY_SIZE {
+                history.pop_front();
+            }
+            env.storage().persistent().set(&key, &history);
+            Self::_extend_ttl(env, &key);
+        }
+
+        if !results.is_empty() {
+            Self::_record_round_winners(env, results);
+        }
+    }
+
+    /// Stores who won the round for head-to-head queries, keeping the last
+    /// ROUND_HISTORY_SIZE rounds and deleting the entry that falls out of the window
+    fn _record_round_winners(env: &Env, results: Map<Address, bool>) {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&ExtDataKey::RoundWinnersCount)
+            .unwrap_or(0);
+
+        let key = ExtDataKey::RoundWinners(count);
+        env.storage().persistent().set(&key, &results);
+        Self::_extend_ttl(env, &key);
+        if count >= ROUND_HISTORY_SIZE {
+            env.storage()
+                .persistent()
+                .remove(&ExtDataKey::RoundWinners(count - ROUND_HISTORY_SIZE));
+        }
+
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::RoundWinnersCount, &(count + 1));
+    }
+
+    /// Returns (rounds `a` won, rounds `b` won) among recent resolved rounds both played in
+    /// A user won a round if it paid them more than they staked (refunds don't count)
+    /// Storage: one entry per resolved round with bets, holding an (address, won) pair per
+    /// participant; only the last 50 are kept, so this reads at most 50 entries
+    pub fn get_head_to_head(env: Env, a: Address, b: Address) -> (u32, u32) {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&ExtDataKey::RoundWinnersCount)
+            .unwrap_or(0);
+
+        let (mut wins_a, mut wins_b) = (0u32, 0u32);
+        for index in count.saturating_sub(ROUND_HISTORY_SIZE)..count {
+            let results: Option<Map<Address, bool>> = env
+                .storage()
+                .persistent()
+                .get(&ExtDataKey::RoundWinners(index));
+            let Some(results) = results else {
+                continue;
+            };
+            if let (Some(won_a), Some(won_b)) = (results.get(a.clone()), results.get(b.clone())) {
+                wins_a += won_a as u32;
+                wins_b += won_b as u32;
+            }
+        }
+
+        (wins_a, wins_b)
+    }
+
+    /// Resolves Up/Down mode round
+    fn _resolve_updown_mode(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+        resolver: Option<Address>,
+    ) -> Result<(), ContractError> {
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
+
+        let price_unchanged = Self::_within_dead_zone(env, round, final_price)?;
+        let price_went_up = !price_unchanged && final_price > round.price_start;
+        let price_went_down = !price_unchanged && final_price < round.price_start;
+
+        // A one-sided market has no counterparty, so it resolves as a no-contest
+        let one_sided = round.pool_up == 0 || round.pool_down == 0;
+
+        if price_unchanged
+            && !one_sided
+            && Self::get_draw_policy(env.clone()) == DrawPolicy::HouseKeeps
+        {
+            Self::_record_house_draw(env, positions, round)?;
+        } else if price_unchanged || one_sided {
+            Self::_record_refunds(env, positions)?;
+        } else if price_went_up {
+            Self::_record_winnings(
+                env,
+                positions,
+                BetSide::Up,
+                round.pool_up,
+                round.pool_down,
+                resolver,
+            )?;
+        } else if price_went_down {
+            Self::_record_winnings(
+                env,
+                positions,
+                BetSide::Down,
+                round.pool_down,
+                round.pool_up,
+                resolver,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true when the price move is within the dead zone and counts as unchanged
+    /// dead zone = price_start * threshold_bps / 10000 (an exact match is always unchanged)
+    fn _within_dead_zone(
+        env: &Env,
+        round: &Round,
+        final_price: u128,
+    ) -> Result<bool, ContractError> {
+        let threshold_bps = Self::get_price_move_threshold_bps(env.clone());
+        let dead_zone = round
+            .price_start
+            .checked_mul(threshold_bps as u128)
+            .ok_or(ContractError::Overflow)?
+            / BPS_DENOMINATOR as u128;
+
+        Ok(final_price.abs_diff(round.price_start) <= dead_zone)
+    }
+
+    /// Resolves Precision/Legends mode round
+    /// No predictions: the round is simply cleared with no payouts or stats
+    /// With a tolerance band, guesses within the band split the pot by stake
+    /// Otherwise (or if none qualify) awards full pot to closest guess(es); ties split evenly,
+    /// so a multi-way exact match pays the exact matchers only. The split's rounding dust goes
+    /// to the earliest tied guess so the full pot is paid out
+    fn _resolve_precision_mode(env: &Env, final_price: u128) -> Result<(), ContractError> {
+        let predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(env));
+
+        // No predictions means no pot and no participants to record stats for
+        if predictions.is_empty() {
+            return Ok(());
+        }
+
+        // Calculate total pot
+        let mut total_pot: i128 = 0;
+        for i in 0..predictions.len() {
+            if let Some(pred) = predictions.get(i) {
+                total_pot = total_pot
+                    .checked_add(pred.amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        // Within a tolerance band, every qualifying guess wins a stake-weighted share
+        let tolerance = Self::get_precision_tolerance(env.clone());
+        if tolerance > 0 {
+            let mut in_band: Vec<PrecisionPrediction> = Vec::new(env);
+            for pred in predictions.iter() {
+                if pred.predicted_price.abs_diff(final_price) <= tolerance {
+                    in_band.push_back(pred);
+                }
+            }
+
+            if !in_band.is_empty() {
+                Self::_pay_stake_weighted(env, &in_band, total_pot)?;
+                return Self::_record_precision_losses(env, &predictions, &in_band);
+            }
+        }
+
+        // Find minimum difference and collect all winners
+        let mut min_diff: Option<u128> = None;
+        let mut winners: Vec<PrecisionPrediction> = Vec::new(env);
+
+        for i in 0..predictions.len() {
+            if let Some(pred) = predictions.get(i) {
+                // Calculate absolute difference using checked arithmetic
+                let diff = if pred.predicted_price >= final_price {
+                    pred.predicted_price
+                        .checked_sub(final_price)
+                        .ok_or(ContractError::Overflow)?
+                } else {
+                    final_price
+                        .checked_sub(pred.predicted_price)
+                        .ok_or(ContractError::Overflow)?
+                };
+
+                match min_diff {
+                    None => {
+                        // First prediction
+                        min_diff = Some(diff);
+                        winners.push_back(pred.clone());
+                    }
+                    Some(current_min) => {
+                        if diff < current_min {
+                            // New winner found, clear previous winners
+                            min_diff = Some(diff);
+                            winners = Vec::new(env);
+                            winners.push_back(pred.clone());
+                        } else if diff == current_min {
+                            // Tie - add to winners
+                            winners.push_back(pred.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        match Self::get_tie_break_mode(env.clone()) {
+            // Predictions are in submission order, so keep the first
+            TieBreakMode::Earliest => winners = winners.slice(0..winners.len().min(1)),
+            TieBreakMode::Random if winners.len() > 1 => {
+                let index = Self::_draw_tied_winner(env, &winners)?;
+                winners = winners.slice(index..index + 1);
+            }
+            _ => {}
+        }
+
+        // Distribute winnings to winner(s)
+        if !winners.is_empty() && total_pot > 0 {
+            let winner_count = winners.len() as i128;
+            let payout_per_winner = total_pot / winner_count;
+            let dust = total_pot % winner_count;
+
+            for i in 0..winners.len() {
+                if let Some(winner) = winners.get(i) {
+                    // Predictions are in submission order, so index 0 is the earliest guess
+                    let payout = if i == 0 {
+                        payout_per_winner + dust
+                    } else {
+                        payout_per_winner
+                    };
+                    Self::_credit_pending(env, winner.user.clone(), payout)?;
+                    Self::_update_stats_volume(env, winner.user.clone(), winner.amount, payout)?;
+
+                    Self::_update_stats_win(env, winner.user.clone());
+                }
+            }
+
+            // Update stats for losers
+            Self::_record_precision_losses(env, &predictions, &winners)?;
+        }
+
+        Ok(())
+    }
+
+    /// Picks one tied winner with probability proportional to stake
+    /// The seed is derived from the ledger sequence and the round's start price, so the draw is
+    /// deterministic and predictable by anyone who can read or influence ledger data. It is NOT
+    /// cryptographically secure and is only suitable for low-stakes virtual markets.
+    fn _draw_tied_winner(
+        env: &Env,
+        winners: &Vec<PrecisionPrediction>,
+    ) -> Result<u32, ContractError> {
+        let round: Round = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveRound)
+            .ok_or(ContractError::NoActiveRound)?;
+
+        let mut total_stake: i128 = 0;
+        for winner in winners.iter() {
+            total_stake = total_stake
+                .checked_add(winner.amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+        if total_stake <= 0 {
+            return Ok(0);
+        }
+
+        // splitmix64 finalizer over the seed inputs
+        let mut seed =
+            (env.ledger().sequence() as u64) ^ (round.price_start as u64).rotate_left(32);
+        seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        seed ^= seed >> 31;
+
+        // Walk the cumulative stakes until the drawn ticket is covered
+        let ticket = (seed as u128 % total_stake as u128) as i128;
+        let mut cumulative: i128 = 0;
+        for (index, winner) in winners.iter().enumerate() {
+            cumulative = cumulative.saturating_add(winner.amount);
+            if ticket < cumulative {
+                return Ok(index as u32);
+            }
+        }
+
+        Ok(winners.len() - 1)
+    }
+
+    /// Resolves a PrecisionWeighted round
+    /// Every guess earns a share of the pot proportional to 1 / (distance + 1)
+    /// Rounding dust goes to the closest guess (first in prediction order on ties)
+    /// A guess counts as a win when its payout exceeds its stake
+    fn _resolve_precision_weighted_mode(env: &Env, final_price: u128) -> Result<(), ContractError> {
+        let predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(env));
+
+        if predictions.is_empty() {
+            return Ok(());
+        }
+
+        let mut total_pot: i128 = 0;
+        let mut total_weight: i128 = 0;
+        let mut weights: Vec<i128> = Vec::new(env);
+        for pred in predictions.iter() {
+            total_pot = total_pot
+                .checked_add(pred.amount)
+                .ok_or(ContractError::Overflow)?;
+
+            let distance = pred.predicted_price.abs_diff(final_price);
+            let weight = WEIGHT_SCALE / distance.saturating_add(1);
+            let weight = i128::try_from(weight).map_err(|_| ContractError::Overflow)?;
+            total_weight = total_weight
+                .checked_add(weight)
+                .ok_or(ContractError::Overflow)?;
+            weights.push_back(weight);
+        }
+
+        if total_weight == 0 {
+            return Self::_record_precision_refunds(env, predictions);
+        }
+
+        // Shares are rounded down; remember the closest guess to receive the dust
+        let mut payouts: Vec<i128> = Vec::new(env);
+        let mut distributed: i128 = 0;
+        let mut closest: u32 = 0;
+        for i in 0..predictions.len() {
+            let weight = weights.get(i).unwrap_or(0);
+            let payout = weight
+                .checked_mul(total_pot)
+                .ok_or(ContractError::Overflow)?
+                / total_weight;
+            distributed = distributed
+                .checked_add(payout)
+                .ok_or(ContractError::Overflow)?;
+            payouts.push_back(payout);
+
+            if weight > weights.get(closest).unwrap_or(0) {
+                closest = i;
+            }
+        }
+
+        let dust = total_pot
+            .checked_sub(distributed)
+            .ok_or(ContractError::Overflow)?;
+        if let Some(payout) = payouts.get(closest) {
+            let payout = payout.checked_add(dust).ok_or(ContractError::Overflow)?;
+            payouts.set(closest, payout);
+        }
+
+        for (i, pred) in predictions.iter().enumerate() {
+            let payout = payouts.get(i as u32).unwrap_or(0);
+            if payout > 0 {
+                Self::_credit_pending(env, pred.user.clone(), payout)?;
+            }
+            Self::_update_stats_volume(env, pred.user.clone(), pred.amount, payout)?;
+            if payout > pred.amount {
+                Self::_update_stats_win(env, pred.user);
+            } else {
+                Self::_update_stats_loss(env, pred.user);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Splits `pot` among `winners` in proportion to their stakes
+    /// Rounding dust goes to the largest stake (first in prediction order on ties)
+    fn _pay_stake_weighted(
+        env: &Env,
+        winners: &Vec<PrecisionPrediction>,
+        pot: i128,
+    ) -> Result<(), ContractError> {
+        let mut winning_stake: i128 = 0;
+        for winner in winners.iter() {
+            winning_stake = winning_stake
+                .checked_add(winner.amount)
+                .ok_or(ContractError::Overflow)?;
+        }
+        if winning_stake == 0 {
+            return Ok(());
+        }
+
+        let mut distributed: i128 = 0;
+        let mut largest: Option<PrecisionPrediction> = None;
+        for winner in winners.iter() {
+            let payout = winner
+                .amount
+                .checked_mul(pot)
+                .ok_or(ContractError::Overflow)?
+                / winning_stake;
+            distributed = distributed
+                .checked_add(payout)
+                .ok_or(ContractError::Overflow)?;
+
+            Self::_credit_pending(env, winner.user.clone(), payout)?;
+            Self::_update_stats_volume(env, winner.user.clone(), winner.amount, payout)?;
+            Self::_update_stats_win(env, winner.user.clone());
+
+            let is_largest = match &largest {
+                Some(current) => winner.amount > current.amount,
+                None => true,
+            };
+            if is_largest {
+                largest = Some(winner);
+            }
+        }
+
+        let dust = pot
+            .checked_sub(distributed)
+            .ok_or(ContractError::Overflow)?;
+        if dust > 0 {
+            if let Some(winner) = largest {
+                Self::_credit_pending(env, winner.user.clone(), dust)?;
+                Self::_update_stats_volume(env, winner.user, 0, dust)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records a loss for every prediction whose user is not among `winners`
+    fn _record_precision_losses(
+        env: &Env,
+        predictions: &Vec<PrecisionPrediction>,
+        winners: &Vec<PrecisionPrediction>,
+    ) -> Result<(), ContractError> {
+        for pred in predictions.iter() {
+            let is_winner = winners.iter().any(|w| w.user == pred.user);
+            if !is_winner {
+                Self::_update_stats_volume(env, pred.user.clone(), pred.amount, 0)?;
+                Self::_update_stats_loss(env, pred.user);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Claims pending winnings and adds to balance
+    pub fn claim_winnings(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let key = DataKey::PendingWinnings(user.clone());
+        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+
+        if pending == 0 {
+            return Ok(0);
+        }
+
+        // Pending winnings are removed before the payout, and the lock stops a
+        // payout hook (e.g. a token transfer) from claiming again mid-flight
+        Self::_lock_claims(&env)?;
+        let new_balance = Self::_pay_pending(&env, &user)?;
+        Self::_unlock_claims(&env);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("winnings"), symbol_short!("claimed")),
+            (user, pending, new_balance),
+        );
+
+        Ok(pending)
+    }
+
+    /// Claims every pending winnings entry for the user in one call
+    /// Credits the combined total to their balance and returns it
+    pub fn claim_all(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        let mut total: i128 = 0;
+        for key in Self::_pending_keys(&env, &user).iter() {
+            let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            total = total.checked_add(pending).ok_or(ContractError::Overflow)?;
+            env.storage().persistent().remove(&key);
+        }
+
+        if total == 0 {
+            return Ok(0);
+        }
+
+        Self::_remove_pending_claimant(&env, &user);
+        Self::_consume_compoundable(&env, &user, total);
+        Self::_lock_claims(&env)?;
+        let new_balance = Self::_pay_out(&env, &user, total)?;
+        Self::_unlock_claims(&env);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("winnings"), symbol_short!("claimall")),
+            (user, total, new_balance),
+        );
+
+        Ok(total)
+    }
+
+    /// Moves a user's pending winnings to the treasury once they have sat unclaimed for the
+    /// claim expiry since they were last credited (admin only); returns the amount swept
+    pub fn sweep_unclaimed(env: Env, user: Address) -> Result<i128, ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let key = DataKey::PendingWinnings(user.clone());
+        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if pending == 0 {
+            return Ok(0);
+        }
+
+        let credited_key = DataKey::LastCredited(user.clone());
+        let last_credited: u32 = env.storage().persistent().get(&credited_key).unwrap_or(0);
+        let expiry = Self::get_claim_expiry(env.clone());
+        if env.ledger().sequence() < last_credited.saturating_add(expiry) {
+            return Err(ContractError::ClaimNotExpired);
+        }
+
+        env.storage().persistent().remove(&key);
+        env.storage().persistent().remove(&credited_key);
+        Self::_remove_pending_claimant(&env, &user);
+        Self::_consume_compoundable(&env, &user, pending);
+
+        let treasury = Self::get_treasury(env.clone()).ok_or(ContractError::AdminNotSet)?;
+        Self::_pay_out(&env, &treasury, pending)?;
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("winnings"), symbol_short!("swept")),
+            (user, pending, treasury),
+        );
+
+        Ok(pending)
+    }
+
+    /// Sets how many ledgers pending winnings may sit unclaimed before they can be swept
+    /// (admin only)
+    pub fn set_claim_expiry(env: Env, ledgers: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let old = Self::get_claim_expiry(env.clone());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ClaimExpiry, &ledgers);
+
+        Self::_publish_config_change(
+            &env,
+            "claim_expiry",
+            old,
+            Self::get_claim_expiry(env.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Returns how many ledgers pending winnings may sit unclaimed (~30 days if unset)
+    pub fn get_claim_expiry(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimExpiry)
+            .unwrap_or(DEFAULT_CLAIM_EXPIRY)
+    }
+
+    /// Marks a claim payout as in progress, failing if one already is
+    /// Held in temporary storage so a stuck lock expires instead of blocking claims forever
+    fn _lock_claims(env: &Env) -> Result<(), ContractError> {
+        if env.storage().temporary().has(&DataKey::ClaimLock) {
+            return Err(ContractError::Reentrancy);
+        }
+        env.storage().temporary().set(&DataKey::ClaimLock, &true);
+
+        Ok(())
+    }
+
+    /// Clears the claim payout lock
+    fn _unlock_claims(env: &Env) {
+        env.storage().temporary().remove(&DataKey::ClaimLock);
+    }
+
+    /// Returns every storage key that may hold pending winnings for the user
+    /// Pending winnings currently accumulate under a single key per user
+    fn _pending_keys(env: &Env, user: &Address) -> Vec<DataKey> {
+        let mut keys = Vec::new(env);
+        keys.push_back(DataKey::PendingWinnings(user.clone()));
+        keys
+    }
+
+    /// Records refunds when price unchanged
+    fn _record_refunds(
+        env: &Env,
+        positions: Map<Address, UserPosition>,
+    ) -> Result<(), ContractError> {
+        let keys: Vec<Address> = positions.keys();
+
+        for i in 0..keys.len() {
+            if let Some(user) = keys.get(i) {
+                if let Some(position) = positions.get(user.clone()) {
+                    Self::_credit_pending(env, user.clone(), position.amount)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends both pools of a drawn round to the treasury, recording a loss for every bettor
+    fn _record_house_draw(
+        env: &Env,
+        positions: Map<Address, UserPosition>,
+        round: &Round,
+    ) -> Result<(), ContractError> {
+        let pot = round
+            .pool_up
+            .checked_add(round.pool_down)
+            .ok_or(ContractError::Overflow)?;
+        Self::_credit_treasury(env, pot)?;
+
+        for (user, position) in positions.iter() {
+            Self::_update_stats_volume(env, user.clone(), position.amount, 0)?;
+            Self::_update_stats_loss(env, user);
+        }
+
+        Ok(())
+    }
+
+    /// Records refunds for every precision prediction
+    fn _record_precision_refunds(
+        env: &Env,
+        predictions: Vec<PrecisionPrediction>,
+    ) -> Result<(), ContractError> {
+        for pred in predictions.iter() {
+            Self::_credit_pending(env, pred.user.clone(), pred.amount)?;
+        }
+
+        Ok(())
+    }
+
+    /// Records winnings for winning side
+    /// Formula: payout = bet + (bet / winning_pool) * (losing_pool - fee)
+    /// Rounding dust goes to the largest winning stake (first by key order on ties), or
+    /// under `RoundingMode::Nearest` one stroop each to the largest fractional remainders
+    fn _record_winnings(
+        env: &Env,
+        positions: Map<Address, UserPosition>,
+        winning_side: BetSide,
+        winning_pool: i128,
+        losing_pool: i128,
+        resolver: Option<Address>,
+    ) -> Result<(), ContractError> {
+        if winning_pool == 0 {
+            return Ok(());
+        }
+
+        // Skim the house fee and resolver reward off the losing pool before distribution
+        let fee = Self::_collect_fee(env, losing_pool)?;
+        let reward = match resolver {
+            Some(resolver) => {
+                let reward = Self::_resolver_reward_on(env, losing_pool)?;
+                if reward > 0 {
+                    Self::_credit_pending(env, resolver, reward)?;
+                }
+                reward
+            }
+            None => 0,
+        };
+        let losing_pool = losing_pool
+            .checked_sub(fee)
+            .and_then(|pool| pool.checked_sub(reward))
+            .ok_or(ContractError::Overflow)?;
+
+        let keys: Vec<Address> = positions.keys();
+        let mut distributed: i128 = 0;
+        let mut largest_winner: Option<(Address, i128)> = None;
+        let round_nearest = Self::get_rounding_mode(env.clone()) == RoundingMode::Nearest;
+        let mut remainders: Vec<(Address, i128)> = Vec::new(env);
+
+        for i in 0..keys.len() {
+            if let Some(user) = keys.get(i) {
+                if let Some(position) = positions.get(user.clone()) {
+                    if position.side == winning_side {
+                        let share_numerator = position
+                            .amount
+                            .checked_mul(losing_pool)
+                            .ok_or(ContractError::Overflow)?;
+                        let share = share_numerator / winning_pool;
+                        if round_nearest {
+                            remainders.push_back((user.clone(), share_numerator % winning_pool));
+                        }
+                        let payout = position
+                            .amount
+                            .checked_add(share)
+                            .ok_or(ContractError::Overflow)?;
+
+                        distributed = distributed
+                            .checked_add(share)
+                            .ok_or(ContractError::Overflow)?;
+                        let is_largest = match &largest_winner {
+                            Some((_, amount)) => position.amount > *amount,
+                            None => true,
+                        };
+                        if is_largest {
+                            largest_winner = Some((user.clone(), position.amount));
+                        }
+
+                        Self::_credit_pending(env, user.clone(), payout)?;
+                        Self::_add_compoundable(env, &user, payout)?;
+                        Self::_update_stats_volume(env, user.clone(), position.amount, payout)?;
+
+                        Self::_update_stats_win(env, user);
+                    } else {
+                        Self::_update_stats_volume(env, user.clone(), position.amount, 0)?;
+                        Self::_update_stats_loss(env, user);
+                    }
+                }
+            }
+        }
+
+        // Award rounding dust so the full pot is paid out
+        let mut dust = losing_pool
+            .checked_sub(distributed)
+            .ok_or(ContractError::Overflow)?;
+        if round_nearest {
+            // Largest remainder: one stroop each to the shares that lost the most to flooring
+            while dust > 0 {
+                let mut best: Option<(u32, i128)> = None;
+                for i in 0..remainders.len() {
+                    if let Some((_, remainder)) = remainders.get(i) {
+                        if remainder > 0 && best.is_none_or(|(_, top)| remainder > top) {
+                            best = Some((i, remainder));
+                        }
+                    }
+                }
+                let Some((index, _)) = best else {
+                    break;
+                };
+                if let Some((user, _)) = remainders.get(index) {
+                    Self::_credit_pending(env, user.clone(), 1)?;
+                    Self::_add_compoundable(env, &user, 1)?;
+                    Self::_update_stats_volume(env, user.clone(), 0, 1)?;
+                    remainders.set(index, (user, 0));
+                }
+                dust -= 1;
+            }
+        } else if dust > 0 {
+            if let Some((user, _)) = largest_winner {
+                Self::_credit_pending(env, user.clone(), dust)?;
+                Self::_add_compoundable(env, &user, dust)?;
+                Self::_update_stats_volume(env, user, 0, dust)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds `amount` to the user's pending winnings
+    fn _credit_pending(env: &Env, user: Address, amount: i128) -> Result<(), ContractError> {
+        let key = DataKey::PendingWinnings(user.clone());
+        let existing_pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let new_pending = existing_pending
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&key, &new_pending);
+        Self::_extend_ttl(env, &key);
+
+        let credited_key = DataKey::LastCredited(user.clone());
+        env.storage()
+            .persistent()
+            .set(&credited_key, &env.ledger().sequence());
+        Self::_extend_ttl(env, &credited_key);
+
+        if new_pending > 0 {
+            Self::_add_pending_claimant(env, &user);
+        }
+
+        Ok(())
+    }
+
+    /// Moves the user's pending winnings into their balance, returning the new balance
+    fn _pay_pending(env: &Env, user: &Address) -> Result<i128, ContractError> {
+        let key = DataKey::PendingWinnings(user.clone());
+        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if pending == 0 {
+            return Ok(Self::_stake_balance(env, user));
+        }
+
+        env.storage().persistent().remove(&key);
+        Self::_remove_pending_claimant(env, user);
+        Self::_consume_compoundable(env, user, pending);
+        Self::_pay_out(env, user, pending)
+    }
+
+    /// Moves `amount` of the user's pending winnings into their balance
+    fn _pay_pending_amount(env: &Env, user: &Address, amount: i128) -> Result<i128, ContractError> {
+        let key = DataKey::PendingWinnings(user.clone());
+        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let remaining = pending.checked_sub(amount).ok_or(ContractError::Overflow)?;
+
+        if remaining == 0 {
+            env.storage().persistent().remove(&key);
+            Self::_remove_pending_claimant(env, user);
+        } else {
+            env.storage().persistent().set(&key, &remaining);
+        }
+        Self::_consume_compoundable(env, user, amount);
+        Self::_pay_out(env, user, amount)
+    }
+
+    /// Adds the user to the pending claimants set in the next free slot, if not already in it
+    fn _add_pending_claimant(env: &Env, user: &Address) {
+        let slot_key = ExtDataKey::ClaimantSlot(user.clone());
+        if env.storage().persistent().has(&slot_key) {
+            Self::_extend_ttl(env, &slot_key);
+            return;
+        }
+
+        let slot = Self::get_pending_claimant_count(env.clone());
+        let claimant_key = ExtDataKey::Claimant(slot);
+        env.storage().persistent().set(&claimant_key, user);
+        Self::_extend_ttl(env, &claimant_key);
+        env.storage().persistent().set(&slot_key, &slot);
+        Self::_extend_ttl(env, &slot_key);
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::ClaimantCount, &(slot + 1));
+    }
+
+    /// Drops the user from the pending claimants set once they have claimed
+    /// The last claimant moves into the freed slot so removal stays O(1)
+    fn _remove_pending_claimant(env: &Env, user: &Address) {
+        let slot_key = ExtDataKey::ClaimantSlot(user.clone());
+        let Some(slot) = env.storage().persistent().get::<_, u32>(&slot_key) else {
+            return;
+        };
+
+        let last = Self::get_pending_claimant_count(env.clone()).saturating_sub(1);
+        if slot != last {
+            let moved: Option<Address> =
+                env.storage().persistent().get(&ExtDataKey::Claimant(last));
+            if let Some(moved) = moved {
+                let claimant_key = ExtDataKey::Claimant(slot);
+                env.storage().persistent().set(&claimant_key, &moved);
+                Self::_extend_ttl(env, &claimant_key);
+                let moved_key = ExtDataKey::ClaimantSlot(moved);
+                env.storage().persistent().set(&moved_key, &slot);
+                Self::_extend_ttl(env, &moved_key);
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&ExtDataKey::Claimant(last));
+        env.storage().persistent().remove(&slot_key);
+        env.storage()
+            .persistent()
+            .set(&ExtDataKey::ClaimantCount, &last);
+    }
+
+    /// Returns the house fee that would be taken from `pool`
+    fn _fee_on(env: &Env, pool: i128) -> Result<i128, ContractError> {
+        let fee_bps = Self::get_fee_bps(env.clone());
+        if fee_bps == 0 || pool <= 0 {
+            return Ok(0);
+        }
+
+        Ok(pool
+            .checked_mul(fee_bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / BPS_DENOMINATOR)
+    }
+
+    /// Returns the resolver reward that would be taken from `pool`
+    fn _resolver_reward_on(env: &Env, pool: i128) -> Result<i128, ContractError> {
+        let reward_bps = Self::get_resolver_reward_bps(env.clone());
+        if reward_bps == 0 || pool <= 0 {
+            return Ok(0);
+        }
+
+        Ok(pool
+            .checked_mul(reward_bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / BPS_DENOMINATOR)
+    }
+
+    /// Returns what is left of a losing pool after the house fee and resolver reward
+    /// Estimates can't know which path will settle the round, so they assume the reward is paid
+    fn _net_losing_pool(env: &Env, pool: i128) -> Result<i128, ContractError> {
+        let deductions = Self::_fee_on(env, pool)?
+            .checked_add(Self::_resolver_reward_on(env, pool)?)
+            .ok_or(ContractError::Overflow)?;
+
+        pool.checked_sub(deductions).ok_or(ContractError::Overflow)
+    }
+
+    /// Credits the house fee on `pool` to the treasury and returns the fee amount
+    fn _collect_fee(env: &Env, pool: i128) -> Result<i128, ContractError> {
+        let fee = Self::_fee_on(env, pool)?;
+        if fee == 0 {
+            return Ok(0);
+        }
+
+        Self::_credit_treasury(env, fee)?;
+
+        Ok(fee)
+    }
+
+    /// Pays `amount` to the treasury and adds it to the accumulated house fees
+    fn _credit_treasury(env: &Env, amount: i128) -> Result<(), ContractError> {
+        let treasury = Self::get_treasury(env.clone()).ok_or(ContractError::AdminNotSet)?;
+        Self::_pay_out(env, &treasury, amount)?;
+
+        let accumulated = Self::get_accumulated_fees(env.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::AccumulatedFees, &accumulated);
+
+        Ok(())
+    }
+
+    /// Loads user stats, lazily migrating entries written before newer fields existed
+    /// Missing fields default to zero
+    pub(crate) fn _load_stats(env: &Env, user: Address) -> UserStats {
+        let key = DataKey::UserStats(user);
+        let fields: Map<Symbol, Val> = match env.storage().persistent().get(&key) {
+            Some(fields) => fields,
+            None => Map::new(env),
+        };
+
+        UserStats {
+            total_wins: stat_field(env, &fields, "total_wins"),
+            total_losses: stat_field(env, &fields, "total_losses"),
+            current_streak: stat_field(env, &fields, "current_streak"),
+            best_streak: stat_field(env, &fields, "best_streak"),
+            total_wagered: stat_field(env, &fields, "total_wagered"),
+            total_won: stat_field(env, &fields, "total_won"),
+            current_loss_streak: stat_field(env, &fields, "current_loss_streak"),
+            worst_loss_streak: stat_field(env, &fields, "worst_loss_streak"),
+            rounds_played: stat_field(env, &fields, "rounds_played"),
+        }
+    }
+
+    /// Returns every user with an Up/Down position or precision prediction in the active round
+    fn _participants(env: &Env) -> Vec<Address> {
+        let positions: Map<Address, UserPosition> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UpDownPositions)
+            .unwrap_or(Map::new(env));
+        let predictions: Vec<PrecisionPrediction> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrecisionPositions)
+            .unwrap_or(Vec::new(env));
+
+        let mut users = positions.keys();
+        for pred in predictions.iter() {
+            users.push_back(pred.user);
+        }
+
+        users
+    }
+
+    /// Counts the resolving round for every participant, whatever their outcome
+    fn _record_rounds_played(env: &Env) {
+        for user in Self::_participants(env).iter() {
+            let key = DataKey::UserStats(user.clone());
+            let mut stats = Self::_load_stats(env, user);
+            stats.rounds_played = stats.rounds_played.saturating_add(1);
+            env.storage().persistent().set(&key, &stats);
+        }
+    }
+
+    /// Adds resolved stake and payout volume to the user's stats
+    pub(crate) fn _update_stats_volume(
+        env: &Env,
+        user: Address,
+        wagered: i128,
+        won: i128,
+    ) -> Result<(), ContractError> {
+        let key = DataKey::UserStats(user.clone());
+        let mut stats = Self::_load_stats(env, user);
+
+        stats.total_wagered = stats
+            .total_wagered
+            .checked_add(wagered)
+            .ok_or(ContractError::Overflow)?;
+        stats.total_won = stats
+            .total_won
+            .checked_add(won)
+            .ok_or(ContractError::Overflow)?;
+
+        env.storage().persistent().set(&key, &stats);
+
+        Ok(())
+    }
+
+    pub(crate) fn _update_stats_win(env: &Env, user: Address) {
+        let key = DataKey::UserStats(user.clone());
+        let mut stats = Self::_load_stats(env, user.clone());
+
+        stats.total_wins += 1;
+        stats.current_streak += 1;
+        stats.current_loss_streak = 0;
+
+        if stats.current_streak > stats.best_streak {
+            stats.best_streak = stats.current_streak;
+            Self::_update_leaderboard(env, user.clone(), stats.best_streak);
+        }
+
+        env.storage().persistent().set(&key, &stats);
+
+        // The streak grows by one per win, so each milestone is reached once per streak
+        if let Some(bonus) = Self::get_streak_bonuses(env.clone()).get(stats.current_streak) {
+            Self::_award_streak_bonus(env, user, stats.current_streak, bonus);
+        }
+    }
+
+    /// Credits a streak milestone bonus to pending winnings
+    /// Bonuses are newly issued vXLM and are skipped once they would exceed the supply cap
+    /// They are skipped entirely with a stake token, as custody holds only deposited tokens
+    fn _award_streak_bonus(env: &Env, user: Address, streak: u32, bonus: i128) {
+        if Self::get_stake_token(env.clone()).is_some() {
+            return;
+        }
+
+        let Some(new_supply) = Self::get_total_supply(env.clone()).checked_add(bonus) else {
+            return;
+        };
+        let max_supply = Self::get_max_supply(env.clone());
+        if max_supply > 0 && new_supply > max_supply {
+            return;
+        }
+
+        // Supply only grows once the bonus was actually credited
+        if Self::_credit_pending(env, user.clone(), bonus).is_err() {
+            return;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalSupply, &new_supply);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("streak"), symbol_short!("bonus")),
+            (user, streak, bonus),
+        );
+    }
+
+    /// Re-ranks the user on the best-streak leaderboard, evicting the lowest entry when full
+    /// Equal streaks keep their existing order, so earlier achievers rank higher
+    fn _update_leaderboard(env: &Env, user: Address, best_streak: u32) {
+        let mut leaderboard = Self::get_leaderboard(env.clone());
+
+        if let Some(index) = leaderboard.iter().position(|(addr, _)| addr == user) {
+            leaderboard.remove(index as u32);
+        }
+
+        let mut index = leaderboard.len();
+        while index > 0 {
+            match leaderboard.get(index - 1) {
+                Some((_, streak)) if streak < best_streak => index -= 1,
+                _ => break,
+            }
+        }
+
+        if index < LEADERBOARD_SIZE {
+            leaderboard.insert(index, (user, best_streak));
+            while leaderboard.len() > LEADERBOARD_SIZE {
+                leaderboard.pop_back();
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Leaderboard, &leaderboard);
+        }
+    }
+
+    pub(crate) fn _update_stats_loss(env: &Env, user: Address) {
+        let key = DataKey::UserStats(user.clone());
+        let mut stats = Self::_load_stats(env, user);
+
+        stats.total_losses += 1;
+        stats.current_streak = 0;
+        stats.current_loss_streak += 1;
+
+        if stats.current_loss_streak > stats.worst_loss_streak {
+            stats.worst_loss_streak = stats.current_loss_streak;
+        }
+
+        env.storage().persistent().set(&key, &stats);
+    }
+
+    /// Returns the vXLM granted by mint_initial (1000 vXLM if unset)
+    pub fn get_initial_grant(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::InitialGrant)
+            .unwrap_or(DEFAULT_INITIAL_GRANT)
+    }
+
+    /// Mints the configured initial grant for new users (one-time only, virtual vXLM only)
+    pub fn mint_initial(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+
+        Self::_grant_initial(&env, user)
+    }
+
+    /// Returns true once the user has a balance entry, i.e. mint_initial would grant nothing
+    /// Unlike balance() == 0, this stays true after the balance is spent down to zero
+    pub fn has_minted(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Balance(user))
+    }
+
+    /// Mints the initial grant for each user who has not received it yet (admin only)
+    /// Users with an existing balance are skipped; returns each user's resulting balance
+    /// Virtual vXLM only, like mint_initial
+    pub fn batch_mint_initial(env: Env, users: Vec<Address>) -> Result<Vec<i128>, ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+
+        let mut balances = Vec::new(&env);
+        for user in users.iter() {
+            balances.push_back(Self::_grant_initial(&env, user)?);
+        }
+
+        Ok(balances)
+    }
+
+    /// Grants the initial amount unless the user already has a balance entry
+    /// Returns the user's balance either way
+    fn _grant_initial(env: &Env, user: Address) -> Result<i128, ContractError> {
+        Self::_require_virtual(env)?;
+
+        let key = DataKey::Balance(user.clone());
+
+        if let Some(existing_balance) = env.storage().persistent().get(&key) {
+            return Ok(existing_balance);
+        }
+
+        let initial_amount = Self::get_initial_grant(env.clone());
+        Self::_increase_supply(env, initial_amount)?;
+        Self::_set_balance(env, user, initial_amount);
+
+        Ok(initial_amount)
+    }
+
+    /// Mints `amount` vXLM to `to` outside the one-time grant (admin only)
+    /// Respects the supply cap if set; returns the recipient's new balance
+    /// Rejected with VirtualBalanceOnly once a stake token is configured
+    pub fn admin_mint(env: Env, to: Address, amount: i128) -> Result<i128, ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
+
+        admin.require_auth();
+        Self::_require_virtual(&env)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
+
+        let new_balance = Self::balance(env.clone(), to.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_increase_supply(&env, amount)?;
+        Self::_set_balance(&env, to.clone(), new_balance);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("token"), symbol_short!("mint")),
+            (to, amount, new_balance),
+        );
+
+        Ok(new_balance)
+    }
+
+    /// Grants the faucet amount to `user` once the cooldown since their last claim has passed
+    /// Returns the amount granted; unavailable once a stake token is configured
+    pub fn faucet(env: Env, user: Address) -> Result<i128, ContractError> {
+        user.require_auth();
+        Self::_require_virtual(&env)?;
+
+        let (cooldown, amount) = Self::get_faucet_config(env.clone());
+        let current_ledger = env.ledger().sequence();
+
+        let last_key = DataKey::LastFaucet(user.clone());
+        let last_claim: Option<u32> = env.storage().persistent().get(&last_key);
+        if let Some(last_claim) = last_claim {
+            if current_ledger < last_claim.saturating_add(cooldown) {
+                return Err(ContractError::FaucetCooldownActive);
+            }
+        }
+
+        let new_balance = Self::balance(env.clone(), user.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        Self::_increase_supply(&env, amount)?;
+        Self::_set_balance(&env, user.clone(), new_balance);
+
+        env.storage().persistent().set(&last_key, &current_ledger);
+        Self::_extend_ttl(&env, &last_key);
 
-        // Emit resolution event
         #[allow(deprecated)]
         env.events().publish(
-            (symbol_short!("round"), symbol_short!("resolved")),
-            payload.price,
+            (symbol_short!("faucet"), symbol_short!("claimed")),
+            (user, amount, new_balance),
         );
 
-        Ok(())
+        Ok(amount)
     }
 
-    /// Resolves Up/Down mode round
-    fn _resolve_updown_mode(
-        env: &Env,
-        round: &Round,
-        final_price: u128,
+    /// Sets the faucet cooldown in ledgers and the vXLM granted per claim (admin only)
+    pub fn set_faucet_config(
+        env: Env,
+        cooldown_ledgers: u32,
+        amount: i128,
     ) -> Result<(), ContractError> {
-        let positions: Map<Address, UserPosition> = env
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::UpDownPositions)
-            .unwrap_or(Map::new(env));
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-        let price_went_up = final_price > round.price_start;
-        let price_went_down = final_price < round.price_start;
-        let price_unchanged = final_price == round.price_start;
+        admin.require_auth();
 
-        if price_unchanged {
-            Self::_record_refunds(env, positions)?;
-        } else if price_went_up {
-            Self::_record_winnings(env, positions, BetSide::Up, round.pool_up, round.pool_down)?;
-        } else if price_went_down {
-            Self::_record_winnings(
-                env,
-                positions,
-                BetSide::Down,
-                round.pool_down,
-                round.pool_up,
-            )?;
+        let old = Self::get_faucet_config(env.clone());
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidGrant);
         }
 
+        env.storage()
+            .persistent()
+            .set(&DataKey::FaucetCooldown, &cooldown_ledgers);
+        env.storage()
+            .persistent()
+            .set(&DataKey::FaucetAmount, &amount);
+
+        Self::_publish_config_change(
+            &env,
+            "faucet_config",
+            old,
+            Self::get_faucet_config(env.clone()),
+        );
+
         Ok(())
     }
 
-    /// Resolves Precision/Legends mode round
-    /// Awards full pot to closest guess(es); ties split evenly
-    fn _resolve_precision_mode(env: &Env, final_price: u128) -> Result<(), ContractError> {
-        let predictions: Vec<PrecisionPrediction> = env
+    /// Returns the faucet (cooldown_ledgers, amount), defaulting to ~1 day and 100 vXLM
+    pub fn get_faucet_config(env: Env) -> (u32, i128) {
+        let cooldown: u32 = env
             .storage()
             .persistent()
-            .get(&DataKey::PrecisionPositions)
-            .unwrap_or(Vec::new(env));
-
-        // If no predictions, nothing to resolve
-        if predictions.is_empty() {
-            return Ok(());
-        }
+            .get(&DataKey::FaucetCooldown)
+            .unwrap_or(DEFAULT_FAUCET_COOLDOWN);
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FaucetAmount)
+            .unwrap_or(DEFAULT_FAUCET_AMOUNT);
 
-        // Find minimum difference and collect all winners
-        let mut min_diff: Option<u128> = None;
-        let mut winners: Vec<PrecisionPrediction> = Vec::new(env);
+        (cooldown, amount)
+    }
 
-        for i in 0..predictions.len() {
-            if let Some(pred) = predictions.get(i) {
-                // Calculate absolute difference using checked arithmetic
-                let diff = if pred.predicted_price >= final_price {
-                    pred.predicted_price
-                        .checked_sub(final_price)
-                        .ok_or(ContractError::Overflow)?
-                } else {
-                    final_price
-                        .checked_sub(pred.predicted_price)
-                        .ok_or(ContractError::Overflow)?
-                };
+    /// Transfers vXLM from one user to another
+    /// Self-transfers are validated but leave the balance unchanged
+    /// Stake token holdings move through the token contract instead; rejected in that mode
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+        Self::_require_virtual(&env)?;
 
-                match min_diff {
-                    None => {
-                        // First prediction
-                        min_diff = Some(diff);
-                        winners.push_back(pred.clone());
-                    }
-                    Some(current_min) => {
-                        if diff < current_min {
-                            // New winner found, clear previous winners
-                            min_diff = Some(diff);
-                            winners = Vec::new(env);
-                            winners.push_back(pred.clone());
-                        } else if diff == current_min {
-                            // Tie - add to winners
-                            winners.push_back(pred.clone());
-                        }
-                    }
-                }
-            }
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
         }
 
-        // Calculate total pot
-        let mut total_pot: i128 = 0;
-        for i in 0..predictions.len() {
-            if let Some(pred) = predictions.get(i) {
-                total_pot = total_pot
-                    .checked_add(pred.amount)
-                    .ok_or(ContractError::Overflow)?;
-            }
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(ContractError::InsufficientBalance);
         }
 
-        // Distribute winnings to winner(s)
-        if !winners.is_empty() && total_pot > 0 {
-            let winner_count = winners.len() as i128;
-            let payout_per_winner = total_pot / winner_count;
+        if from == to {
+            return Ok(());
+        }
 
-            for i in 0..winners.len() {
-                if let Some(winner) = winners.get(i) {
-                    let key = DataKey::PendingWinnings(winner.user.clone());
-                    let existing_pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-                    let new_pending = existing_pending
-                        .checked_add(payout_per_winner)
-                        .ok_or(ContractError::Overflow)?;
-                    env.storage().persistent().set(&key, &new_pending);
+        let new_from_balance = from_balance
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        let new_to_balance = Self::balance(env.clone(), to.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
 
-                    Self::_update_stats_win(env, winner.user.clone());
-                }
-            }
+        Self::_set_balance(&env, from.clone(), new_from_balance);
+        Self::_set_balance(&env, to.clone(), new_to_balance);
 
-            // Update stats for losers
-            for i in 0..predictions.len() {
-                if let Some(pred) = predictions.get(i) {
-                    let is_winner = winners.iter().any(|w| w.user == pred.user);
-                    if !is_winner {
-                        Self::_update_stats_loss(env, pred.user.clone());
-                    }
-                }
-            }
-        }
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("token"), symbol_short!("transfer")),
+            (from, to, amount),
+        );
 
         Ok(())
     }
 
-    /// Claims pending winnings and adds to balance
-    pub fn claim_winnings(env: Env, user: Address) -> i128 {
-        user.require_auth();
+    /// Burns vXLM from a user's balance and returns the new balance
+    /// Only virtual balances can be burned; rejected once a stake token is configured
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<i128, ContractError> {
+        from.require_auth();
+        Self::_require_virtual(&env)?;
 
-        let key = DataKey::PendingWinnings(user.clone());
-        let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(ContractError::InvalidBetAmount);
+        }
 
-        if pending == 0 {
-            return 0;
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(ContractError::InsufficientBalance);
         }
 
-        let current_balance = Self::balance(env.clone(), user.clone());
-        let new_balance = current_balance + pending;
-        Self::_set_balance(&env, user.clone(), new_balance);
+        let new_balance = from_balance
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+        let new_supply = Self::get_total_supply(env.clone())
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
 
-        env.storage().persistent().remove(&key);
+        Self::_set_balance(&env, from, new_balance);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalSupply, &new_supply);
 
-        pending
+        Ok(new_balance)
     }
 
-    /// Records refunds when price unchanged
-    fn _record_refunds(
-        env: &Env,
-        positions: Map<Address, UserPosition>,
-    ) -> Result<(), ContractError> {
-        let keys: Vec<Address> = positions.keys();
-
-        for i in 0..keys.len() {
-            if let Some(user) = keys.get(i) {
-                if let Some(position) = positions.get(user.clone()) {
-                    let key = DataKey::PendingWinnings(user.clone());
-                    let existing_pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-                    let new_pending = existing_pending
-                        .checked_add(position.amount)
-                        .ok_or(ContractError::Overflow)?;
-                    env.storage().persistent().set(&key, &new_pending);
-                }
-            }
-        }
+    /// Returns the total amount of vXLM in circulation
+    pub fn get_total_supply(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
 
-        Ok(())
+    /// Returns the contract's own balance: the stake token it holds in custody, or its
+    /// vXLM balance entry in virtual mode (stakes are debited from bettors, so normally 0)
+    /// Outside an active round, total supply equals the sum of all balances and pending
+    /// winnings plus this balance; during a round the open pools make up the difference
+    pub fn get_contract_balance(env: Env) -> i128 {
+        Self::_stake_balance(&env, &env.current_contract_address())
     }
 
-    /// Records winnings for winning side
-    /// Formula: payout = bet + (bet / winning_pool) * losing_pool
-    fn _record_winnings(
-        env: &Env,
-        positions: Map<Address, UserPosition>,
-        winning_side: BetSide,
-        winning_pool: i128,
-        losing_pool: i128,
-    ) -> Result<(), ContractError> {
-        if winning_pool == 0 {
-            return Ok(());
-        }
+    /// Sets the cap on total vXLM supply (admin only); 0 means unlimited
+    pub fn set_max_supply(env: Env, max_supply: i128) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::AdminNotSet)?;
 
-        let keys: Vec<Address> = positions.keys();
+        admin.require_auth();
 
-        for i in 0..keys.len() {
-            if let Some(user) = keys.get(i) {
-                if let Some(position) = positions.get(user.clone()) {
-                    if position.side == winning_side {
-                        let share_numerator = position
-                            .amount
-                            .checked_mul(losing_pool)
-                            .ok_or(ContractError::Overflow)?;
-                        let share = share_numerator / winning_pool;
-                        let payout = position
-                            .amount
-                            .checked_add(share)
-                            .ok_or(ContractError::Overflow)?;
+        let old = Self::get_max_supply(env.clone());
 
-                        let key = DataKey::PendingWinnings(user.clone());
-                        let existing_pending: i128 =
-                            env.storage().persistent().get(&key).unwrap_or(0);
-                        let new_pending = existing_pending
-                            .checked_add(payout)
-                            .ok_or(ContractError::Overflow)?;
-                        env.storage().persistent().set(&key, &new_pending);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxSupply, &max_supply);
 
-                        Self::_update_stats_win(env, user);
-                    } else {
-                        Self::_update_stats_loss(env, user);
-                    }
-                }
-            }
-        }
+        Self::_publish_config_change(&env, "max_supply", old, Self::get_max_supply(env.clone()));
 
         Ok(())
     }
 
-    pub(crate) fn _update_stats_win(env: &Env, user: Address) {
-        let key = DataKey::UserStats(user);
-        let mut stats: UserStats = env.storage().persistent().get(&key).unwrap_or(UserStats {
-            total_wins: 0,
-            total_losses: 0,
-            current_streak: 0,
-            best_streak: 0,
-        });
+    /// Returns the cap on total vXLM supply (0 if unlimited)
+    pub fn get_max_supply(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxSupply)
+            .unwrap_or(0)
+    }
 
-        stats.total_wins += 1;
-        stats.current_streak += 1;
+    /// Adds newly minted vXLM to the total supply, enforcing the supply cap if set
+    fn _increase_supply(env: &Env, amount: i128) -> Result<(), ContractError> {
+        let new_supply = Self::get_total_supply(env.clone())
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
 
-        if stats.current_streak > stats.best_streak {
-            stats.best_streak = stats.current_streak;
+        let max_supply = Self::get_max_supply(env.clone());
+        if max_supply > 0 && new_supply > max_supply {
+            return Err(ContractError::SupplyCapReached);
         }
 
-        env.storage().persistent().set(&key, &stats);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalSupply, &new_supply);
+
+        Ok(())
     }
 
-    pub(crate) fn _update_stats_loss(env: &Env, user: Address) {
-        let key = DataKey::UserStats(user);
-        let mut stats: UserStats = env.storage().persistent().get(&key).unwrap_or(UserStats {
-            total_wins: 0,
-            total_losses: 0,
-            current_streak: 0,
-            best_streak: 0,
-        });
+    /// Returns user's vXLM balance (virtual only; stake token holdings live in the token contract)
+    pub fn balance(env: Env, user: Address) -> i128 {
+        let key = DataKey::Balance(user);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
 
-        stats.total_losses += 1;
-        stats.current_streak = 0;
+    /// Returns the number of decimal places used by vXLM amounts
+    pub fn decimals(_env: Env) -> u32 {
+        DECIMALS
+    }
 
-        env.storage().persistent().set(&key, &stats);
+    /// Splits a stroop amount into (is_negative, whole vXLM, fractional stroops) for display
+    /// e.g. 1000_0000000 -> (false, 1000, 0) and -1 -> (true, 0, 1)
+    /// The sign is returned on its own so sub-unit negative amounts keep it
+    pub fn to_display(_env: Env, amount: i128) -> (bool, u128, u32) {
+        let unit = 10u128.pow(DECIMALS);
+        let magnitude = amount.unsigned_abs();
+        (amount < 0, magnitude / unit, (magnitude % unit) as u32)
     }
 
-    /// Mints 1000 vXLM for new users (one-time only)
-    pub fn mint_initial(env: Env, user: Address) -> i128 {
-        user.require_auth();
+    /// Returns the token contract holding stakes in custody, if real tokens are used
+    pub fn get_stake_token(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::StakeToken)
+    }
 
-        let key = DataKey::Balance(user.clone());
+    /// Fails with VirtualBalanceOnly when a stake token replaces the virtual vXLM ledger
+    fn _require_virtual(env: &Env) -> Result<(), ContractError> {
+        if Self::get_stake_token(env.clone()).is_some() {
+            return Err(ContractError::VirtualBalanceOnly);
+        }
 
-        if let Some(existing_balance) = env.storage().persistent().get(&key) {
-            return existing_balance;
+        Ok(())
+    }
+
+    /// Returns the balance stakes are drawn from: stake token balance or virtual vXLM
+    fn _stake_balance(env: &Env, user: &Address) -> i128 {
+        match Self::get_stake_token(env.clone()) {
+            Some(stake_token) => token::Client::new(env, &stake_token).balance(user),
+            None => Self::balance(env.clone(), user.clone()),
         }
+    }
 
-        let initial_amount: i128 = 1000_0000000;
-        env.storage().persistent().set(&key, &initial_amount);
+    /// Moves `amount` of the user's funds into the contract's custody
+    fn _take_stake(env: &Env, user: &Address, amount: i128) -> Result<(), ContractError> {
+        match Self::get_stake_token(env.clone()) {
+            Some(stake_token) => {
+                token::Client::new(env, &stake_token).transfer(
+                    user,
+                    env.current_contract_address(),
+                    &amount,
+                );
+            }
+            None => {
+                let new_balance = Self::balance(env.clone(), user.clone())
+                    .checked_sub(amount)
+                    .ok_or(ContractError::Overflow)?;
+                Self::_set_balance(env, user.clone(), new_balance);
+            }
+        }
 
-        initial_amount
+        Ok(())
     }
 
-    /// Returns user's vXLM balance
-    pub fn balance(env: Env, user: Address) -> i128 {
-        let key = DataKey::Balance(user);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    /// Pays `amount` out of custody to the user and returns their new balance
+    fn _pay_out(env: &Env, user: &Address, amount: i128) -> Result<i128, ContractError> {
+        match Self::get_stake_token(env.clone()) {
+            Some(stake_token) => {
+                let client = token::Client::new(env, &stake_token);
+                client.transfer(&env.current_contract_address(), user, &amount);
+                Ok(client.balance(user))
+            }
+            None => {
+                let new_balance = Self::balance(env.clone(), user.clone())
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+                Self::_set_balance(env, user.clone(), new_balance);
+                Ok(new_balance)
+            }
+        }
     }
 
     pub(crate) fn _set_balance(env: &Env, user: Address, amount: i128) {
         let key = DataKey::Balance(user);
         env.storage().persistent().set(&key, &amount);
+        Self::_extend_ttl(env, &key);
+    }
+
+    /// Bumps a persistent entry's TTL so it is not archived while in use
+    pub(crate) fn _extend_ttl<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(
+                key,
+                TTL_THRESHOLD_LEDGERS,
+                TTL_EXTEND_TO_LEDGERS,
+            );
+        }
     }
 }
+
+/// Reads a stats field from its stored map, defaulting when absent or mistyped
+fn stat_field<T: TryFromVal<Env, Val> + Default>(
+    env: &Env,
+    fields: &Map<Symbol, Val>,
+    name: &str,
+) -> T {
+    fields
+        .get(Symbol::new(env, name))
+        .and_then(|val| T::try_from_val(env, &val).ok())
+        .unwrap_or_default()
+}
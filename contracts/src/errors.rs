@@ -45,4 +45,62 @@ pub enum ContractError {
     StaleOracleData = 18,
     /// Oracle payload round_id doesn't match ActiveRound
     InvalidOracleRound = 19,
+    /// User has no position in the active round
+    NoPosition = 20,
+    /// Fee exceeds the maximum allowed basis points
+    InvalidFee = 21,
+    /// Betting is paused by the admin
+    ContractPaused = 22,
+    /// No admin transfer has been proposed
+    NoPendingAdmin = 23,
+    /// Not enough oracle price submissions to reach quorum
+    InsufficientOraclePrices = 24,
+    /// Oracle quorum must be greater than zero
+    InvalidQuorum = 25,
+    /// Oracle missed the resolution window; round can only be refunded
+    ResolutionWindowExpired = 26,
+    /// Initial grant must be greater than zero
+    InvalidGrant = 27,
+    /// Faucet was claimed too recently
+    FaucetCooldownActive = 28,
+    /// Setting cannot change while a round is active
+    RoundInProgress = 29,
+    /// Round has reached its maximum number of entries
+    RoundFull = 30,
+    /// Minting would push total supply over the configured cap
+    SupplyCapReached = 31,
+    /// Final price deviates too far from the round's start price
+    PriceDeviationTooLarge = 32,
+    /// Call is only available while test mode is enabled
+    NotInTestMode = 33,
+    /// Payout multiplier after the bet would fall below the caller's minimum
+    SlippageExceeded = 34,
+    /// A claim is already in progress
+    Reentrancy = 35,
+    /// Pending winnings have not sat unclaimed long enough to be swept
+    ClaimNotExpired = 36,
+    /// Amount exceeds the user's current stake
+    AmountExceedsStake = 37,
+    /// Admin and oracle must be different addresses
+    AdminOracleConflict = 38,
+    /// User must wait for the bet cooldown before betting again
+    BetCooldownActive = 39,
+    /// No resolution has been announced for the active round
+    ResolutionNotAnnounced = 40,
+    /// A resolution has already been announced for the active round
+    ResolutionAlreadyAnnounced = 41,
+    /// Resolution is under review or must go through announce_resolution / confirm_resolution
+    ReviewWindowActive = 42,
+    /// Emergency mode has not been enabled
+    EmergencyNotActive = 43,
+    /// Emergency mode is enabled but its timelock has not elapsed
+    EmergencyTimelockActive = 44,
+    /// Signature is malformed and cannot be checked against the oracle signing key
+    InvalidSignature = 45,
+    /// The auto-compound list is full
+    AutoCompoundFull = 46,
+    /// Call only works on virtual vXLM balances, but a stake token is configured
+    VirtualBalanceOnly = 47,
+    /// Per-round entry cap must be greater than zero
+    InvalidRoundCap = 48,
 }
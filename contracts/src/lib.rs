@@ -12,6 +12,7 @@
 
 mod contract;
 mod errors;
+mod oracle;
 mod types;
 
 #[cfg(test)]
@@ -19,4 +20,8 @@ mod tests;
 
 pub use contract::VirtualTokenContract;
 pub use errors::ContractError;
-pub use types::{BetSide, DataKey, Round, UserPosition, UserStats};
+pub use oracle::{PriceOracle, PriceOracleClient};
+pub use types::{
+    BetSide, DataKey, DrawPolicy, ExtDataKey, HistoryEntry, PendingResolution, Portfolio,
+    ResolvedRound, Round, RoundSummary, RoundingMode, TieBreakMode, UserPosition, UserStats,
+};
@@ -0,0 +1,10 @@
+//! Client interface for external price oracle contracts.
+
+use soroban_sdk::{contractclient, Env, Symbol};
+
+/// Interface an oracle contract must implement for rounds to pull their final price
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Returns the latest price for `asset` in stroops
+    fn get_price(env: Env, asset: Symbol) -> u128;
+}
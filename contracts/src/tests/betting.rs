@@ -1,8 +1,8 @@
-//! Tests for bet placement and validation.
+//! Tests for bet placement, withdrawal and validation.
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::BetSide;
+use crate::types::{BetSide, OraclePayload};
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
     Address, Env,
@@ -20,12 +20,12 @@ fn test_place_bet_zero_amount() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
     client.create_round(&1_0000000, &None);
 
     // Try to bet 0 amount - should return error
-    let result = client.try_place_bet(&user, &0, &BetSide::Up);
+    let result = client.try_place_bet(&user, &0, &BetSide::Up, &None);
     assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
 }
 
@@ -41,12 +41,12 @@ fn test_place_bet_negative_amount() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
     client.create_round(&1_0000000, &None);
 
     // Try to bet negative amount - should return error
-    let result = client.try_place_bet(&user, &-100, &BetSide::Up);
+    let result = client.try_place_bet(&user, &-100, &BetSide::Up, &None);
     assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
 }
 
@@ -62,11 +62,11 @@ fn test_place_bet_no_active_round() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Try to bet without active round - should return error
-    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up);
+    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up, &None);
     assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
 }
 
@@ -86,7 +86,7 @@ fn test_place_bet_after_round_ended() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create round (default bet window is 6 ledgers)
@@ -98,7 +98,7 @@ fn test_place_bet_after_round_ended() {
     });
 
     // Try to bet after bet window closed - should return error
-    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up);
+    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up, &None);
     assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
 }
 
@@ -114,12 +114,12 @@ fn test_place_bet_insufficient_balance() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user); // Has 1000 vXLM
     client.create_round(&1_0000000, &None);
 
     // Try to bet more than balance - should return error
-    let result = client.try_place_bet(&user, &2000_0000000, &BetSide::Up);
+    let result = client.try_place_bet(&user, &2000_0000000, &BetSide::Up, &None);
     assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
 }
 
@@ -135,18 +135,65 @@ fn test_place_bet_twice_same_round() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
     client.create_round(&1_0000000, &None);
 
     // First bet succeeds
-    client.place_bet(&user, &100_0000000, &BetSide::Up);
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
 
     // Second bet should fail with error
-    let result = client.try_place_bet(&user, &50_0000000, &BetSide::Down);
+    let result = client.try_place_bet(&user, &50_0000000, &BetSide::Down, &None);
     assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
 }
 
+#[test]
+fn test_place_bet_pct() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+
+    assert_eq!(
+        client.try_place_bet_pct(&alice, &10_001, &BetSide::Up),
+        Err(Ok(ContractError::InvalidBetAmount))
+    );
+
+    // Half of the balance
+    assert_eq!(
+        client.place_bet_pct(&alice, &5000, &BetSide::Up),
+        500_0000000
+    );
+    assert_eq!(client.balance(&alice), 500_0000000);
+    assert_eq!(
+        client.get_user_position(&alice).unwrap().amount,
+        500_0000000
+    );
+
+    // All-in
+    assert_eq!(
+        client.place_bet_pct(&bob, &10_000, &BetSide::Down),
+        1000_0000000
+    );
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(client.get_user_position(&bob).unwrap().amount, 1000_0000000);
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.pool_up, 500_0000000);
+    assert_eq!(round.pool_down, 1000_0000000);
+}
+
 #[test]
 fn test_get_user_position_no_bet() {
     let env = Env::default();
@@ -159,3 +206,574 @@ fn test_get_user_position_no_bet() {
     let position = client.get_user_position(&user);
     assert_eq!(position, None);
 }
+
+#[test]
+fn test_withdraw_bet() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+
+    // Alice exits before the bet window closes
+    let refunded = client.withdraw_bet(&alice);
+    assert_eq!(refunded, 100_0000000);
+    assert_eq!(client.balance(&alice), 1000_0000000);
+    assert_eq!(client.get_user_position(&alice), None);
+
+    // Only the Up pool shrinks
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.pool_up, 0);
+    assert_eq!(round.pool_down, 50_0000000);
+
+    // Alice can bet again after withdrawing
+    client.place_bet(&alice, &20_0000000, &BetSide::Down, &None);
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.pool_down, 70_0000000);
+}
+
+#[test]
+fn test_withdraw_bet_no_active_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let result = client.try_withdraw_bet(&user);
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+}
+
+#[test]
+fn test_withdraw_bet_without_position() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&1_0000000, &None);
+
+    let result = client.try_withdraw_bet(&user);
+    assert_eq!(result, Err(Ok(ContractError::NoPosition)));
+}
+
+#[test]
+fn test_withdraw_bet_after_bet_window() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&user);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
+
+    // Bet window closes at ledger 6
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 6;
+    });
+
+    let result = client.try_withdraw_bet(&user);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+    assert_eq!(client.balance(&user), 900_0000000);
+}
+
+#[test]
+fn test_reduce_bet_partially() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+
+    let refunded = client.reduce_bet(&alice, &30_0000000);
+    assert_eq!(refunded, 30_0000000);
+    assert_eq!(client.balance(&alice), 930_0000000);
+
+    let position = client.get_user_position(&alice).unwrap();
+    assert_eq!(position.amount, 70_0000000);
+    assert_eq!(position.side, BetSide::Up);
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.pool_up, 70_0000000);
+    assert_eq!(round.pool_down, 50_0000000);
+}
+
+#[test]
+fn test_reduce_bet_to_zero_removes_position() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down, &None);
+
+    client.reduce_bet(&alice, &40_0000000);
+    client.reduce_bet(&alice, &60_0000000);
+
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.balance(&alice), 1000_0000000);
+    assert_eq!(client.get_active_round().unwrap().pool_down, 0);
+}
+
+#[test]
+fn test_reduce_bet_rejections() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+
+    let result = client.try_reduce_bet(&alice, &100_0000001);
+    assert_eq!(result, Err(Ok(ContractError::AmountExceedsStake)));
+
+    let result = client.try_reduce_bet(&alice, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+
+    // Bet window closes at ledger 6
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 6;
+    });
+    let result = client.try_reduce_bet(&alice, &10_0000000);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+
+    assert_eq!(
+        client.get_user_position(&alice).unwrap().amount,
+        100_0000000
+    );
+    assert_eq!(client.balance(&alice), 900_0000000);
+}
+
+#[test]
+fn test_withdraw_precision_prediction() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&2000, &Some(1));
+
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &50_0000000, &2300);
+
+    let refunded = client.withdraw_precision_prediction(&alice);
+    assert_eq!(refunded, 100_0000000);
+    assert_eq!(client.balance(&alice), 1000_0000000);
+    assert_eq!(client.get_user_precision_prediction(&alice), None);
+    assert_eq!(client.get_precision_predictions().len(), 1);
+
+    // Withdrawing twice fails
+    let result = client.try_withdraw_precision_prediction(&alice);
+    assert_eq!(result, Err(Ok(ContractError::NoPosition)));
+
+    // Up/Down withdrawal is rejected on a Precision round
+    let result = client.try_withdraw_bet(&bob);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+}
+
+#[test]
+fn test_withdraw_precision_prediction_from_middle_keeps_order() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&2000, &Some(1));
+
+    client.place_precision_prediction(&alice, &10_0000000, &2100);
+    client.place_precision_prediction(&bob, &20_0000000, &2200);
+    client.place_precision_prediction(&charlie, &30_0000000, &2300);
+
+    assert_eq!(client.withdraw_precision_prediction(&bob), 20_0000000);
+    assert_eq!(client.balance(&bob), 1000_0000000);
+
+    // Remaining entries keep their submission order
+    let predictions = client.get_precision_predictions();
+    assert_eq!(predictions.len(), 2);
+    assert_eq!(predictions.get(0).unwrap().user, alice);
+    assert_eq!(predictions.get(1).unwrap().user, charlie);
+    assert_eq!(
+        client
+            .get_user_precision_prediction(&charlie)
+            .unwrap()
+            .predicted_price,
+        2300
+    );
+}
+
+#[test]
+fn test_withdraw_precision_prediction_after_bet_window() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+
+    // Bet window closes at ledger 6
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 6;
+    });
+
+    let result = client.try_withdraw_precision_prediction(&alice);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+    assert_eq!(client.balance(&alice), 900_0000000);
+    assert!(client.get_user_precision_prediction(&alice).is_some());
+}
+
+#[test]
+fn test_place_bet_round_full() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_max_participants(), 100);
+    let result = client.try_set_max_participants(&0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoundCap)));
+    client.set_max_participants(&2);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&1_0000000, &None);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    // A new bettor is turned away once the round is full
+    let result = client.try_place_bet(&charlie, &100_0000000, &BetSide::Up, &None);
+    assert_eq!(result, Err(Ok(ContractError::RoundFull)));
+    assert_eq!(client.balance(&charlie), 1000_0000000);
+
+    // Existing bettors are not counted as new entries
+    let result = client.try_place_bet(&alice, &50_0000000, &BetSide::Up, &None);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
+
+    // A withdrawal frees a slot
+    client.withdraw_bet(&bob);
+    client.place_bet(&charlie, &100_0000000, &BetSide::Up, &None);
+    assert_eq!(client.get_updown_positions().len(), 2);
+}
+
+#[test]
+fn test_get_participant_count_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_participant_count(), 0);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&1_0000000, &None);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    client.place_bet(&charlie, &10_0000000, &BetSide::Up, &None);
+    assert_eq!(client.get_participant_count(), 3);
+
+    // A second bet from an existing participant adds nobody
+    let result = client.try_place_bet(&alice, &10_0000000, &BetSide::Up, &None);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
+    assert_eq!(client.get_participant_count(), 3);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_participant_count(), 0);
+}
+
+#[test]
+fn test_get_participant_count_precision() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&2000, &Some(1));
+
+    client.place_precision_prediction(&alice, &10_0000000, &2100);
+    client.place_precision_prediction(&bob, &10_0000000, &1900);
+    assert_eq!(client.get_participant_count(), 2);
+
+    client.withdraw_precision_prediction(&alice);
+    assert_eq!(client.get_participant_count(), 1);
+}
+
+#[test]
+fn test_bet_cooldown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.set_bet_cooldown(&20);
+    assert_eq!(client.get_bet_cooldown(), 20);
+
+    // Round 1: bet at ledger 0
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &10_0000000, &BetSide::Up, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 2 opens at ledger 12, still within the cooldown
+    client.create_round(&2000, &Some(1));
+    let result = client.try_place_precision_prediction(&alice, &10_0000000, &2100);
+    assert_eq!(result, Err(Ok(ContractError::BetCooldownActive)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 17;
+    });
+    let result = client.try_place_precision_prediction(&alice, &10_0000000, &2100);
+    assert_eq!(result, Err(Ok(ContractError::BetCooldownActive)));
+
+    // Ledger 20 is past the cooldown
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.cancel_round();
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &10_0000000, &2100);
+}
+
+#[test]
+fn test_bet_cooldown_disabled_by_default() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    assert_eq!(client.get_bet_cooldown(), 0);
+
+    // Withdrawing and betting again in the same ledger is allowed
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &10_0000000, &BetSide::Up, &None);
+    client.withdraw_bet(&alice);
+    client.place_bet(&alice, &10_0000000, &BetSide::Down, &None);
+}
+
+#[test]
+fn test_validate_bet() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    let validate =
+        |user: &Address, amount: i128| client.try_validate_bet(user, &amount, &BetSide::Up);
+
+    assert_eq!(
+        validate(&alice, 100_0000000),
+        Err(Ok(ContractError::NoActiveRound))
+    );
+
+    client.create_round(&2000, &Some(1));
+    assert_eq!(
+        validate(&alice, 100_0000000),
+        Err(Ok(ContractError::WrongModeForPrediction))
+    );
+    client.cancel_round();
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(
+        validate(&alice, 0),
+        Err(Ok(ContractError::InvalidBetAmount))
+    );
+    assert_eq!(
+        validate(&alice, 1000_0000001),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+
+    // Succeeds without auth and without touching state
+    env.set_auths(&[]);
+    assert_eq!(validate(&alice, 100_0000000), Ok(Ok(())));
+    assert_eq!(client.balance(&alice), 1000_0000000);
+    assert_eq!(client.get_user_position(&alice), None);
+    env.mock_all_auths();
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    assert_eq!(
+        validate(&alice, 100_0000000),
+        Err(Ok(ContractError::AlreadyBet))
+    );
+
+    client.set_bet_cooldown(&100);
+    assert_eq!(
+        validate(&alice, 100_0000000),
+        Err(Ok(ContractError::BetCooldownActive))
+    );
+
+    client.set_max_participants(&1);
+    assert_eq!(
+        validate(&bob, 100_0000000),
+        Err(Ok(ContractError::RoundFull))
+    );
+    client.set_max_participants(&100);
+
+    client.pause();
+    assert_eq!(
+        validate(&bob, 100_0000000),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    client.unpause();
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 7;
+    });
+    assert_eq!(
+        validate(&bob, 100_0000000),
+        Err(Ok(ContractError::RoundEnded))
+    );
+}
@@ -0,0 +1,229 @@
+//! Tests for holding real stakes in a Stellar Asset Contract.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env, Map,
+};
+
+/// Deploys the market backed by a fresh stake token and funds `users` with 1000 tokens each
+fn setup<'a>(
+    env: &Env,
+    users: &[&Address],
+) -> (VirtualTokenContractClient<'a>, token::Client<'a>, Address) {
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_admin = token::StellarAssetClient::new(env, &token_id);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &Some(token_id.clone()));
+    for user in users {
+        token_admin.mint(user, &1000_0000000);
+    }
+
+    (client, token::Client::new(env, &token_id), contract_id)
+}
+
+#[test]
+fn test_stake_token_is_stored() {
+    let env = Env::default();
+    let (client, token, _) = setup(&env, &[]);
+
+    assert_eq!(client.get_stake_token(), Some(token.address));
+}
+
+#[test]
+fn test_bets_and_claims_move_real_tokens() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let (client, token, contract_id) = setup(&env, &[&alice, &bob]);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    // Stakes are pulled into the contract's custody
+    assert_eq!(token.balance(&alice), 900_0000000);
+    assert_eq!(token.balance(&bob), 960_0000000);
+    assert_eq!(token.balance(&contract_id), 140_0000000);
+    assert_eq!(client.balance(&alice), 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Winnings are paid out of custody on claim
+    assert_eq!(client.claim_winnings(&alice), 140_0000000);
+    assert_eq!(token.balance(&alice), 1040_0000000);
+    assert_eq!(token.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_precision_prediction_and_withdrawal_move_real_tokens() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let (client, token, contract_id) = setup(&env, &[&alice]);
+
+    client.create_round(&1_0000000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    assert_eq!(token.balance(&contract_id), 100_0000000);
+
+    assert_eq!(client.withdraw_precision_prediction(&alice), 100_0000000);
+    assert_eq!(token.balance(&alice), 1000_0000000);
+    assert_eq!(token.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_cancelled_round_refunds_real_tokens() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let (client, token, contract_id) = setup(&env, &[&alice]);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.cancel_round();
+
+    assert_eq!(client.claim_all(&alice), 100_0000000);
+    assert_eq!(token.balance(&alice), 1000_0000000);
+    assert_eq!(token.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_bet_exceeding_token_balance_fails() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let (client, _, _) = setup(&env, &[&alice]);
+
+    client.create_round(&1_0000000, &None);
+    let result = client.try_place_bet(&alice, &1001_0000000, &BetSide::Up, &None);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+}
+
+#[test]
+fn test_withdraw_treasury_moves_real_tokens() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let ops = Address::generate(&env);
+    let (client, token, _) = setup(&env, &[&alice, &bob]);
+
+    // The treasury signs for the token transfer made on its behalf
+    env.mock_all_auths_allowing_non_root_auth();
+
+    client.set_treasury(&treasury);
+    client.set_fee_bps(&1000); // 10%
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(token.balance(&treasury), 4_0000000);
+
+    assert_eq!(client.withdraw_treasury(&ops, &3_0000000), 1_0000000);
+    assert_eq!(token.balance(&ops), 3_0000000);
+
+    let result = client.try_withdraw_treasury(&ops, &2_0000000);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+}
+
+#[test]
+fn test_streak_bonuses_are_skipped_with_stake_token() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let (client, token, contract_id) = setup(&env, &[&alice, &bob]);
+
+    let mut bonuses = Map::new(&env);
+    bonuses.set(1, 50_0000000);
+    client.set_streak_bonuses(&bonuses);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Alice reached the milestone, but custody only covers the staked tokens
+    assert_eq!(client.get_user_stats(&alice).current_streak, 1);
+    let pending = client.get_pending_winnings(&alice) + client.get_pending_winnings(&bob);
+    assert_eq!(pending, 140_0000000);
+    assert!(token.balance(&contract_id) >= pending);
+    assert_eq!(client.get_total_supply(), 0);
+}
+
+#[test]
+fn test_create_round_waits_for_staked_round() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let (client, token, contract_id) = setup(&env, &[&alice]);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+
+    // Replacing the round would leave Alice's tokens stranded in custody
+    let result = client.try_create_round(&2_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::RoundInProgress)));
+    assert_eq!(client.get_active_round().unwrap().pool_up, 100_0000000);
+
+    client.cancel_round();
+    assert_eq!(client.claim_winnings(&alice), 100_0000000);
+    assert_eq!(token.balance(&contract_id), 0);
+    client.create_round(&2_0000000, &None);
+}
+
+#[test]
+fn test_virtual_balance_calls_rejected_with_stake_token() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let (client, _, _) = setup(&env, &[&alice]);
+
+    let rejected = Err(Ok(ContractError::VirtualBalanceOnly));
+    assert_eq!(client.try_mint_initial(&alice), rejected);
+    assert_eq!(client.try_admin_mint(&alice, &10_0000000), rejected);
+    assert_eq!(client.try_faucet(&alice), rejected);
+    assert_eq!(client.try_burn(&alice, &10_0000000), rejected);
+    assert_eq!(
+        client.try_batch_mint_initial(&vec![&env, alice.clone()]),
+        Err(Ok(ContractError::VirtualBalanceOnly))
+    );
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10_0000000),
+        Err(Ok(ContractError::VirtualBalanceOnly))
+    );
+    assert_eq!(client.get_total_supply(), 0);
+
+    // The portfolio reports the stake token balance instead of the unused vXLM entry
+    assert_eq!(client.get_portfolio(&alice).balance, 1000_0000000);
+    assert_eq!(client.balance(&alice), 0);
+}
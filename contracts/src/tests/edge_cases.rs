@@ -18,7 +18,7 @@ fn test_round_with_no_participants() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create round with no bets
     client.create_round(&1_0000000, &None);
@@ -55,14 +55,14 @@ fn test_round_with_only_one_side() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&alice);
     client.mint_initial(&bob);
 
     // Create round and only bet on UP
     client.create_round(&1_0000000, &None);
-    client.place_bet(&alice, &100_0000000, &BetSide::Up);
-    client.place_bet(&bob, &150_0000000, &BetSide::Up);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &150_0000000, &BetSide::Up, &None);
 
     let round = client.get_active_round().unwrap();
     assert_eq!(round.pool_up, 250_0000000);
@@ -96,12 +96,12 @@ fn test_accumulate_pending_winnings() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&alice);
 
     // Round 1: Alice bets UP and wins
     client.create_round(&1_0000000, &None);
-    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
 
     env.as_contract(&contract_id, || {
         let mut positions = Map::<Address, UserPosition>::new(&env);
@@ -143,7 +143,7 @@ fn test_accumulate_pending_winnings() {
 
     // Round 2: Alice bets and gets refund
     client.create_round(&2_0000000, &None);
-    client.place_bet(&alice, &50_0000000, &BetSide::Down);
+    client.place_bet(&alice, &50_0000000, &BetSide::Down, &None);
 
     // Advance ledger to allow resolution
     env.ledger().with_mut(|li| {
@@ -164,3 +164,137 @@ fn test_accumulate_pending_winnings() {
     assert_eq!(claimed, total_pending);
     assert_eq!(client.get_pending_winnings(&alice), 0);
 }
+
+#[test]
+fn test_one_sided_round_refunds_when_side_loses() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Everyone bets UP, then the price drops
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &150_0000000, &BetSide::Up, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Stakes are refunded instead of stranded in the contract
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 150_0000000);
+    assert_eq!(client.get_user_stats(&alice).total_losses, 0);
+}
+
+#[test]
+fn test_one_sided_down_round_refunds_both_directions() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    // Round 1: only DOWN bets, price goes up
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+
+    // Round 2: only DOWN bets, price goes down
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+
+    // Neither round counts as a win or loss
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.total_wins, 0);
+    assert_eq!(stats.total_losses, 0);
+}
+
+#[test]
+fn test_rounding_dust_goes_to_largest_winner() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.mint_initial(&dave);
+
+    // Winning pool of 7 stroops split across a losing pool of 10 does not divide evenly
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &1, &BetSide::Up, &None);
+    client.place_bet(&bob, &2, &BetSide::Up, &None);
+    client.place_bet(&charlie, &4, &BetSide::Up, &None);
+    client.place_bet(&dave, &10, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Shares: 10/7 = 1, 20/7 = 2, 40/7 = 5 -> 8 distributed, 2 dust to Charlie
+    let alice_payout = client.get_pending_winnings(&alice);
+    let bob_payout = client.get_pending_winnings(&bob);
+    let charlie_payout = client.get_pending_winnings(&charlie);
+
+    assert_eq!(alice_payout, 2);
+    assert_eq!(bob_payout, 4);
+    assert_eq!(charlie_payout, 11);
+    assert_eq!(alice_payout + bob_payout + charlie_payout, 7 + 10);
+}
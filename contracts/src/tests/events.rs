@@ -0,0 +1,374 @@
+//! Tests for events published by the contract.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::types::{BetSide, DrawPolicy, OraclePayload, TieBreakMode};
+use core::fmt::Debug;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger as _},
+    Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Returns the data of the last event, asserting it was published under `expected_topics`
+fn last_event_data(env: &Env, expected_topics: (Symbol, Symbol)) -> Val {
+    let (_, topics, data) = env.events().all().last().expect("event published");
+
+    let expected_topics: Vec<Val> = expected_topics.into_val(env);
+    assert_eq!(topics.len(), expected_topics.len());
+    for (topic, expected) in topics.iter().zip(expected_topics.iter()) {
+        let topic: Symbol = topic.into_val(env);
+        let expected: Symbol = expected.into_val(env);
+        assert_eq!(topic, expected);
+    }
+
+    data
+}
+
+fn resolve_updown_round(final_price: u128) -> (u128, u128, u32, i128, i128) {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: final_price,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    last_event_data(&env, (symbol_short!("round"), symbol_short!("resolved"))).into_val(&env)
+}
+
+#[test]
+fn test_resolved_event_price_up() {
+    let data = resolve_updown_round(1_5000000);
+    assert_eq!(data, (1_5000000, 1_0000000, 1, 100_0000000, 40_0000000));
+}
+
+#[test]
+fn test_resolved_event_price_down() {
+    let data = resolve_updown_round(5000000);
+    assert_eq!(data, (5000000, 1_0000000, 0, 100_0000000, 40_0000000));
+}
+
+#[test]
+fn test_resolved_event_price_unchanged() {
+    let data = resolve_updown_round(1_0000000);
+    assert_eq!(data, (1_0000000, 1_0000000, 2, 100_0000000, 40_0000000));
+}
+
+#[test]
+fn test_resolved_event_for_empty_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let bystander = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let data: (u128, u128, u32, i128, i128) =
+        last_event_data(&env, (symbol_short!("round"), symbol_short!("resolved"))).into_val(&env);
+    assert_eq!(data, (1_5000000, 1_0000000, 1, 0, 0));
+
+    // State is cleared and nothing is recorded in stats
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_participant_count(), 0);
+    assert_eq!(client.get_last_resolved_ledger(), Some(20));
+    assert_eq!(client.get_user_stats(&bystander).rounds_played, 0);
+    assert_eq!(client.get_pending_winnings(&bystander), 0);
+
+    // A new round can be opened straight away
+    client.create_round(&1_5000000, &None);
+    assert!(client.get_active_round().is_some());
+}
+
+#[test]
+fn test_claimed_event() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let pending = client.get_pending_winnings(&alice);
+    client.claim_winnings(&alice);
+
+    let data: (Address, i128, i128) =
+        last_event_data(&env, (symbol_short!("winnings"), symbol_short!("claimed"))).into_val(&env);
+    assert_eq!(data, (alice.clone(), pending, client.balance(&alice)));
+    assert_eq!(data.2, 900_0000000 + 140_0000000);
+
+    // Nothing to claim - no event
+    client.claim_winnings(&bob);
+    assert!(env.events().all().is_empty());
+}
+
+#[test]
+fn test_claim_all_event() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let total = client.claim_all(&alice);
+
+    // A single aggregate event covers the whole claim
+    assert_eq!(env.events().all().len(), 1);
+    let data: (Address, i128, i128) =
+        last_event_data(&env, (symbol_short!("winnings"), symbol_short!("claimall")))
+            .into_val(&env);
+    assert_eq!(data, (alice.clone(), total, client.balance(&alice)));
+}
+
+#[test]
+fn test_pool_update_events() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+
+    let topics = (symbol_short!("pool"), symbol_short!("update"));
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    let data: (i128, i128) = last_event_data(&env, topics.clone()).into_val(&env);
+    assert_eq!(data, (100_0000000, 0));
+
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+    let data: (i128, i128) = last_event_data(&env, topics.clone()).into_val(&env);
+    assert_eq!(data, (100_0000000, 40_0000000));
+
+    client.withdraw_bet(&alice);
+    let data: (i128, i128) = last_event_data(&env, topics).into_val(&env);
+    assert_eq!(data, (0, 40_0000000));
+}
+
+/// Asserts the last event is ("config", "changed") with the given setting and old/new values
+fn assert_config_changed<T>(env: &Env, setting: &str, old: T, new: T)
+where
+    T: TryFromVal<Env, Val> + Debug + PartialEq,
+{
+    let data = last_event_data(env, (symbol_short!("config"), symbol_short!("changed")));
+    let (name, got_old, got_new): (Symbol, T, T) = data.into_val(env);
+    assert_eq!(name, Symbol::new(env, setting));
+    assert_eq!(got_old, old);
+    assert_eq!(got_new, new);
+}
+
+#[test]
+fn test_config_changed_events_for_numeric_setters() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    client.set_windows(&10, &20);
+    assert_config_changed(&env, "windows", (6u32, 12u32), (10u32, 20u32));
+
+    client.set_fee_bps(&250);
+    assert_config_changed(&env, "fee_bps", 0u32, 250u32);
+
+    client.set_resolver_reward_bps(&50);
+    assert_config_changed(&env, "resolver_reward_bps", 0u32, 50u32);
+
+    client.set_expiry_grace(&100);
+    assert_config_changed(&env, "expiry_grace", 17_280u32, 100u32);
+
+    client.set_max_resolve_delay(&500);
+    assert_config_changed(&env, "max_resolve_delay", None, Some(500u32));
+
+    client.set_precision_tolerance(&25);
+    assert_config_changed(&env, "precision_tolerance", 0u128, 25u128);
+
+    client.set_min_participants(&3);
+    assert_config_changed(&env, "min_participants", 0u32, 3u32);
+
+    client.set_price_move_threshold_bps(&10);
+    assert_config_changed(&env, "price_move_threshold_bps", 0u32, 10u32);
+
+    client.set_max_predictions(&50);
+    assert_config_changed(&env, "max_predictions", 100u32, 50u32);
+
+    client.set_max_participants(&40);
+    assert_config_changed(&env, "max_participants", 100u32, 40u32);
+
+    client.set_max_price_deviation_bps(&2000);
+    assert_config_changed(&env, "max_price_deviation_bps", 0u32, 2000u32);
+
+    client.set_oracle_quorum(&2);
+    assert_config_changed(&env, "oracle_quorum", 1u32, 2u32);
+
+    client.set_claim_expiry(&1000);
+    assert_config_changed(&env, "claim_expiry", 30 * 17_280u32, 1000u32);
+
+    client.set_faucet_config(&60, &5_0000000);
+    assert_config_changed(
+        &env,
+        "faucet_config",
+        (17_280u32, 100_0000000i128),
+        (60u32, 5_0000000i128),
+    );
+
+    client.set_max_supply(&1000000_0000000);
+    assert_config_changed(&env, "max_supply", 0i128, 1000000_0000000i128);
+
+    client.set_test_mode(&true);
+    assert_config_changed(&env, "test_mode", false, true);
+}
+
+#[test]
+fn test_config_changed_events_for_address_and_policy_setters() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let new_oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let oracle_contract = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    client.set_oracle(&new_oracle);
+    assert_config_changed(
+        &env,
+        "oracle",
+        Some(oracle.clone()),
+        Some(new_oracle.clone()),
+    );
+
+    // Treasury falls back to the admin until one is set
+    client.set_treasury(&treasury);
+    assert_config_changed(&env, "treasury", Some(admin.clone()), Some(treasury));
+
+    client.set_oracle_contract(&oracle_contract);
+    assert_config_changed(&env, "oracle_contract", None, Some(oracle_contract));
+
+    client.set_tie_break_mode(&TieBreakMode::Earliest);
+    assert_config_changed(
+        &env,
+        "tie_break_mode",
+        TieBreakMode::Split,
+        TieBreakMode::Earliest,
+    );
+
+    client.set_draw_policy(&DrawPolicy::HouseKeeps);
+    assert_config_changed(
+        &env,
+        "draw_policy",
+        DrawPolicy::Refund,
+        DrawPolicy::HouseKeeps,
+    );
+
+    client.add_oracle(&oracle);
+    assert_config_changed(
+        &env,
+        "oracles",
+        Vec::<Address>::new(&env),
+        Vec::from_array(&env, [oracle.clone()]),
+    );
+
+    client.remove_oracle(&oracle);
+    assert_config_changed(
+        &env,
+        "oracles",
+        Vec::from_array(&env, [oracle]),
+        Vec::<Address>::new(&env),
+    );
+}
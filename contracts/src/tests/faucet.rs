@@ -0,0 +1,98 @@
+//! Tests for the vXLM faucet and its cooldown.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+#[test]
+fn test_faucet_claim() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    // Default faucet grants 100 vXLM
+    assert_eq!(client.faucet(&alice), 100_0000000);
+    assert_eq!(client.balance(&alice), 1100_0000000);
+    assert_eq!(client.get_total_supply(), 1100_0000000);
+}
+
+#[test]
+fn test_faucet_cooldown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_faucet_config(&100, &50_0000000);
+    assert_eq!(client.get_faucet_config(), (100, 50_0000000));
+
+    client.faucet(&alice);
+
+    // Claiming again before the cooldown fails
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 99;
+    });
+    let result = client.try_faucet(&alice);
+    assert_eq!(result, Err(Ok(ContractError::FaucetCooldownActive)));
+
+    // Succeeds once the cooldown has passed
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 100;
+    });
+    assert_eq!(client.faucet(&alice), 50_0000000);
+    assert_eq!(client.balance(&alice), 100_0000000);
+}
+
+#[test]
+fn test_faucet_cooldown_is_per_user() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    client.faucet(&alice);
+    assert_eq!(client.faucet(&bob), 100_0000000);
+}
+
+#[test]
+fn test_set_faucet_config_invalid_amount() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let result = client.try_set_faucet_config(&100, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidGrant)));
+}
@@ -0,0 +1,424 @@
+//! Tests for the house fee taken on resolution.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, DrawPolicy, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+#[test]
+fn test_set_fee_bps() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_fee_bps(), 0);
+
+    client.set_fee_bps(&250);
+    assert_eq!(client.get_fee_bps(), 250);
+
+    // Maximum is 10%
+    client.set_fee_bps(&1000);
+    let result = client.try_set_fee_bps(&1001);
+    assert_eq!(result, Err(Ok(ContractError::InvalidFee)));
+    assert_eq!(client.get_fee_bps(), 1000);
+}
+
+#[test]
+fn test_treasury_defaults_to_admin() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert_eq!(client.get_treasury(), None);
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_treasury(), Some(admin));
+
+    client.set_treasury(&treasury);
+    assert_eq!(client.get_treasury(), Some(treasury));
+}
+
+#[test]
+fn test_fee_taken_from_losing_pool() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_treasury(&treasury);
+    client.set_fee_bps(&1000); // 10%
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &200_0000000, &BetSide::Up, &None);
+    client.place_bet(&charlie, &150_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Fee: 150 * 10% = 15, leaving 135 to distribute
+    // Alice: 100 + (100/300)*135 = 145
+    // Bob: 200 + (200/300)*135 = 290
+    assert_eq!(client.get_pending_winnings(&alice), 145_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 290_0000000);
+    assert_eq!(client.balance(&treasury), 15_0000000);
+    assert_eq!(client.get_accumulated_fees(), 15_0000000);
+}
+
+#[test]
+fn test_no_fee_on_refund() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_fee_bps(&500);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Unchanged price refunds in full
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 100_0000000);
+    assert_eq!(client.get_accumulated_fees(), 0);
+}
+
+/// Resolves a round with a 10% fee, leaving 15 vXLM of fees in `treasury`
+fn collect_fees<'a>(env: &Env, treasury: &Address) -> VirtualTokenContractClient<'a> {
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_treasury(treasury);
+    client.set_fee_bps(&1000); // 10%
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &150_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    client
+}
+
+#[test]
+fn test_withdraw_treasury_partial_and_full() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let ops = Address::generate(&env);
+    let client = collect_fees(&env, &treasury);
+    assert_eq!(client.balance(&treasury), 15_0000000);
+
+    let remaining = client.withdraw_treasury(&ops, &5_0000000);
+    assert_eq!(remaining, 10_0000000);
+    assert_eq!(client.balance(&ops), 5_0000000);
+
+    let remaining = client.withdraw_treasury(&ops, &10_0000000);
+    assert_eq!(remaining, 0);
+    assert_eq!(client.balance(&treasury), 0);
+    assert_eq!(client.balance(&ops), 15_0000000);
+
+    // Lifetime fee accounting is unaffected by withdrawals
+    assert_eq!(client.get_accumulated_fees(), 15_0000000);
+}
+
+#[test]
+fn test_withdraw_treasury_rejects_over_withdrawal() {
+    let env = Env::default();
+    let treasury = Address::generate(&env);
+    let ops = Address::generate(&env);
+    let client = collect_fees(&env, &treasury);
+
+    let result = client.try_withdraw_treasury(&ops, &15_0000001);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+
+    let result = client.try_withdraw_treasury(&ops, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+
+    assert_eq!(client.balance(&treasury), 15_0000000);
+    assert_eq!(client.balance(&ops), 0);
+}
+
+#[test]
+fn test_set_resolver_reward_bps() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_resolver_reward_bps(), 0);
+
+    client.set_resolver_reward_bps(&50);
+    assert_eq!(client.get_resolver_reward_bps(), 50);
+
+    let result = client.try_set_resolver_reward_bps(&1001);
+    assert_eq!(result, Err(Ok(ContractError::InvalidFee)));
+}
+
+#[test]
+fn test_resolver_reward_paid_to_oracle() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_treasury(&treasury);
+    client.set_fee_bps(&200); // 2% house fee
+    client.set_resolver_reward_bps(&100); // 1% resolver reward
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    // Estimates net out both the fee and the reward: 100 + 100 * 97 / 200
+    assert_eq!(
+        client.estimate_payout(&BetSide::Up, &100_0000000),
+        148_5000000
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Losing pool of 100: 2 to the treasury, 1 to the oracle, 97 to Alice
+    assert_eq!(client.balance(&treasury), 2_0000000);
+    assert_eq!(client.claim_winnings(&oracle), 1_0000000);
+    assert_eq!(client.balance(&oracle), 1_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 197_0000000);
+}
+
+#[test]
+fn test_resolver_reward_skipped_by_oracle_set_finalization() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let submitter = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_resolver_reward_bps(&100); // 1% resolver reward
+    client.add_oracle(&submitter);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    let projected = client.get_projected_payout(&alice);
+    assert_eq!(projected, 199_0000000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.submit_price(&submitter, &1_5000000);
+    client.finalize_resolution();
+
+    // No one resolved as the oracle, so the reward stays in the pot and Alice gets more
+    assert_eq!(client.get_pending_winnings(&oracle), 0);
+    assert_eq!(client.get_pending_winnings(&submitter), 0);
+    assert!(client.get_pending_winnings(&alice) >= projected);
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+}
+
+/// Runs a drawn Up/Down round under `policy` and returns
+/// (Alice's pending winnings, treasury balance, accumulated fees)
+fn resolve_draw(policy: DrawPolicy) -> (i128, i128, i128) {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_treasury(&treasury);
+    client.set_draw_policy(&policy);
+    assert_eq!(client.get_draw_policy(), policy);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    (
+        client.get_pending_winnings(&alice),
+        client.balance(&treasury),
+        client.get_accumulated_fees(),
+    )
+}
+
+#[test]
+fn test_draw_policy_refund() {
+    assert_eq!(resolve_draw(DrawPolicy::Refund), (100_0000000, 0, 0));
+}
+
+#[test]
+fn test_draw_policy_house_keeps() {
+    assert_eq!(
+        resolve_draw(DrawPolicy::HouseKeeps),
+        (0, 150_0000000, 150_0000000)
+    );
+}
+
+#[test]
+fn test_sweep_unclaimed_after_expiry() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_treasury(&treasury);
+    assert_eq!(client.get_claim_expiry(), 30 * 17_280);
+    client.set_claim_expiry(&100);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Credited at ledger 12, so sweepable from ledger 112
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 111;
+    });
+    let result = client.try_sweep_unclaimed(&alice);
+    assert_eq!(result, Err(Ok(ContractError::ClaimNotExpired)));
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 112;
+    });
+    assert_eq!(client.sweep_unclaimed(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.balance(&treasury), 200_0000000);
+    assert!(client.get_pending_claimants().is_empty());
+
+    // Nothing left to sweep
+    assert_eq!(client.sweep_unclaimed(&alice), 0);
+}
@@ -0,0 +1,367 @@
+//! Tests for the resolved round history.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::types::{BetSide, HistoryEntry, OraclePayload, ResolvedRound, RoundMode};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+#[test]
+fn test_round_history_records_resolved_rounds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert!(client.get_round_history().is_empty());
+
+    // Round 1: Up/Down
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 2: Precision
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &10_0000000, &2100);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2050,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    let history = client.get_round_history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap(),
+        ResolvedRound {
+            price_start: 1_0000000,
+            final_price: 1_5000000,
+            pool_up: 100_0000000,
+            pool_down: 40_0000000,
+            mode: RoundMode::UpDown,
+            resolved_ledger: 12,
+            outcome: 1,
+        }
+    );
+    assert_eq!(
+        history.get(1).unwrap(),
+        ResolvedRound {
+            price_start: 2000,
+            final_price: 2050,
+            pool_up: 0,
+            pool_down: 0,
+            mode: RoundMode::Precision,
+            resolved_ledger: 24,
+            outcome: 3,
+        }
+    );
+}
+
+#[test]
+fn test_round_history_evicts_oldest() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // Resolve 52 empty rounds, each starting at a distinct price
+    for i in 0..52u32 {
+        let start_ledger = i * 12;
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger;
+        });
+        client.create_round(&(1_0000000 + i as u128), &None);
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: 1_0000000,
+            timestamp: env.ledger().timestamp(),
+            round_id: start_ledger,
+        });
+    }
+
+    // Only the latest 50 remain, oldest first
+    let history = client.get_round_history();
+    assert_eq!(history.len(), 50);
+    assert_eq!(history.get(0).unwrap().price_start, 1_0000000 + 2);
+    assert_eq!(history.get(49).unwrap().price_start, 1_0000000 + 51);
+}
+
+#[test]
+fn test_bet_history_records_resolved_bets() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert!(client.get_bet_history(&alice).is_empty());
+
+    // Up/Down round: Alice wins Bob's stake
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Precision round: Bob is closer and takes the pot
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &10_0000000, &2500);
+    client.place_precision_prediction(&bob, &10_0000000, &2100);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2150,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    let history = client.get_bet_history(&alice);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap(),
+        HistoryEntry {
+            round_start_price: 1_0000000,
+            side_or_predicted: 1,
+            amount: 100_0000000,
+            payout: 150_0000000,
+            resolved_ledger: 12,
+        }
+    );
+    assert_eq!(
+        history.get(1).unwrap(),
+        HistoryEntry {
+            round_start_price: 2000,
+            side_or_predicted: 2500,
+            amount: 10_0000000,
+            payout: 0,
+            resolved_ledger: 24,
+        }
+    );
+
+    let history = client.get_bet_history(&bob);
+    assert_eq!(history.get(0).unwrap().side_or_predicted, 0);
+    assert_eq!(history.get(0).unwrap().payout, 0);
+    assert_eq!(history.get(1).unwrap().payout, 20_0000000);
+}
+
+#[test]
+fn test_bet_history_evicts_oldest() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    // 22 one-sided rounds, each refunded; the start price identifies the round
+    for i in 0..22u32 {
+        let start_ledger = i * 12;
+        client.create_round(&(1_0000000 + i as u128), &None);
+        client.place_bet(&alice, &10_0000000, &BetSide::Up, &None);
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: 2_0000000,
+            timestamp: env.ledger().timestamp(),
+            round_id: start_ledger,
+        });
+    }
+
+    let history = client.get_bet_history(&alice);
+    assert_eq!(history.len(), 20);
+    assert_eq!(history.get(0).unwrap().round_start_price, 1_0000002);
+    assert_eq!(history.get(19).unwrap().round_start_price, 1_0000021);
+    assert_eq!(history.get(19).unwrap().payout, 10_0000000);
+}
+
+#[test]
+fn test_get_round_outcome() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    assert_eq!(client.get_round_outcome(&0), None);
+
+    // Up/Down rounds resolving up, down and unchanged, then a Precision round
+    let rounds: [(u128, u128, Option<u32>); 4] = [
+        (1_0000000, 1_5000000, None),
+        (1_0000000, 5000000, None),
+        (1_0000000, 1_0000000, None),
+        (2000, 2100, Some(1)),
+    ];
+    for (i, (start, end, mode)) in rounds.iter().enumerate() {
+        let start_ledger = i as u32 * 12;
+        client.create_round(start, mode);
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: *end,
+            timestamp: env.ledger().timestamp(),
+            round_id: start_ledger,
+        });
+    }
+
+    assert_eq!(client.get_round_outcome(&0), Some(1));
+    assert_eq!(client.get_round_outcome(&1), Some(0));
+    assert_eq!(client.get_round_outcome(&2), Some(2));
+    assert_eq!(client.get_round_outcome(&3), Some(3));
+    assert_eq!(client.get_round_outcome(&4), None);
+}
+
+/// Opens an Up/Down round at `start_ledger`, places `bets`, and resolves it at `final_price`
+fn play_round(
+    env: &Env,
+    client: &VirtualTokenContractClient,
+    start_ledger: u32,
+    bets: &[(&Address, BetSide)],
+    final_price: u128,
+) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number = start_ledger;
+    });
+    client.create_round(&1_0000000, &None);
+    for (user, side) in bets {
+        client.place_bet(user, &10_0000000, side, &None);
+    }
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = start_ledger + 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: final_price,
+        timestamp: env.ledger().timestamp(),
+        round_id: start_ledger,
+    });
+}
+
+#[test]
+fn test_get_head_to_head() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+    assert_eq!(client.get_head_to_head(&alice, &bob), (0, 0));
+
+    // Alice wins
+    play_round(
+        &env,
+        &client,
+        0,
+        &[(&alice, BetSide::Up), (&bob, BetSide::Down)],
+        1_5000000,
+    );
+    // Bob wins
+    play_round(
+        &env,
+        &client,
+        12,
+        &[(&alice, BetSide::Down), (&bob, BetSide::Up)],
+        1_5000000,
+    );
+    // Bob sits out, so this doesn't count between Alice and Bob
+    play_round(
+        &env,
+        &client,
+        24,
+        &[(&alice, BetSide::Up), (&carol, BetSide::Down)],
+        1_5000000,
+    );
+    // Bob wins
+    play_round(
+        &env,
+        &client,
+        36,
+        &[(&alice, BetSide::Up), (&bob, BetSide::Down)],
+        5000000,
+    );
+    // Unchanged price refunds everyone, so nobody wins
+    play_round(
+        &env,
+        &client,
+        48,
+        &[
+            (&alice, BetSide::Up),
+            (&bob, BetSide::Up),
+            (&carol, BetSide::Down),
+        ],
+        1_0000000,
+    );
+
+    assert_eq!(client.get_head_to_head(&alice, &bob), (1, 2));
+    assert_eq!(client.get_head_to_head(&bob, &alice), (2, 1));
+    assert_eq!(client.get_head_to_head(&alice, &carol), (1, 0));
+    assert_eq!(client.get_head_to_head(&bob, &carol), (0, 0));
+}
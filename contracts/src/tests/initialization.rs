@@ -84,7 +84,7 @@ fn test_initialize() {
     env.mock_all_auths();
 
     // Initialize the contract
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Verify admin and oracle are set
     let stored_admin = client.get_admin();
@@ -93,6 +93,79 @@ fn test_initialize() {
     assert_eq!(stored_oracle, Some(oracle));
 }
 
+#[test]
+fn test_initialize_rejects_invalid_grant() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let result = client.try_initialize(&admin, &oracle, &0, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidGrant)));
+
+    let result = client.try_initialize(&admin, &oracle, &-1, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidGrant)));
+}
+
+#[test]
+fn test_initialize_rejects_admin_as_oracle() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let result = client.try_initialize(&admin, &admin, &1000_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::AdminOracleConflict)));
+    assert_eq!(client.get_admin(), None);
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_admin(), Some(admin));
+    assert_eq!(client.get_oracle(), Some(oracle));
+}
+
+#[test]
+fn test_mint_initial_uses_custom_grant() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &250_0000000, &None);
+    assert_eq!(client.get_initial_grant(), 250_0000000);
+
+    assert_eq!(client.mint_initial(&alice), 250_0000000);
+    assert_eq!(client.balance(&alice), 250_0000000);
+    assert_eq!(client.get_total_supply(), 250_0000000);
+}
+
+#[test]
+fn test_mint_initial_defaults_without_stored_grant() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Deployments predating the configurable grant keep handing out 1000 vXLM
+    assert_eq!(client.get_initial_grant(), 1000_0000000);
+    assert_eq!(client.mint_initial(&alice), 1000_0000000);
+}
+
 #[test]
 fn test_initialize_twice_fails() {
     let env = Env::default();
@@ -105,9 +178,40 @@ fn test_initialize_twice_fails() {
     env.mock_all_auths();
 
     // Initialize once
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Try to initialize again - should return error
-    let result = client.try_initialize(&admin, &oracle);
+    let result = client.try_initialize(&admin, &oracle, &1000_0000000, &None);
     assert_eq!(result, Err(Ok(ContractError::AlreadyInitialized)));
 }
+
+#[test]
+fn test_version() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.version(), 1);
+}
+
+#[test]
+fn test_get_config() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Uninitialized contract has no config yet
+    let result = client.try_get_config();
+    assert_eq!(result, Err(Ok(ContractError::AdminNotSet)));
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_config(), (admin.clone(), oracle.clone(), 6, 12));
+
+    client.set_windows(&10, &20);
+    assert_eq!(client.get_config(), (admin, oracle, 10, 20));
+}
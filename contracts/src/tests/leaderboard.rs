@@ -0,0 +1,129 @@
+//! Tests for the on-chain best-streak leaderboard.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, Vec};
+
+#[test]
+fn test_leaderboard_ordering() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    assert_eq!(client.get_leaderboard().len(), 0);
+
+    // Alice: 1 win, Bob: 3 wins, Charlie: 2 wins
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, alice.clone());
+        for _ in 0..3 {
+            VirtualTokenContract::_update_stats_win(&env, bob.clone());
+        }
+        for _ in 0..2 {
+            VirtualTokenContract::_update_stats_win(&env, charlie.clone());
+        }
+    });
+
+    let leaderboard = client.get_leaderboard();
+    assert_eq!(leaderboard.len(), 3);
+    assert_eq!(leaderboard.get(0), Some((bob.clone(), 3)));
+    assert_eq!(leaderboard.get(1), Some((charlie.clone(), 2)));
+    assert_eq!(leaderboard.get(2), Some((alice.clone(), 1)));
+
+    // A loss does not lower a best streak; a longer run moves Alice to the top
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+        for _ in 0..4 {
+            VirtualTokenContract::_update_stats_win(&env, alice.clone());
+        }
+    });
+
+    let leaderboard = client.get_leaderboard();
+    assert_eq!(leaderboard.len(), 3);
+    assert_eq!(leaderboard.get(0), Some((alice, 4)));
+    assert_eq!(leaderboard.get(1), Some((bob, 3)));
+    assert_eq!(leaderboard.get(2), Some((charlie, 2)));
+}
+
+#[test]
+fn test_leaderboard_evicts_lowest() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    // Fill the board with 10 users at a streak of 2
+    let mut users = Vec::new(&env);
+    for _ in 0..10 {
+        users.push_back(Address::generate(&env));
+    }
+    env.as_contract(&contract_id, || {
+        for user in users.iter() {
+            VirtualTokenContract::_update_stats_win(&env, user.clone());
+            VirtualTokenContract::_update_stats_win(&env, user);
+        }
+    });
+    assert_eq!(client.get_leaderboard().len(), 10);
+
+    // A newcomer tying the lowest entry does not displace anyone
+    let newcomer = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, newcomer.clone());
+        VirtualTokenContract::_update_stats_win(&env, newcomer.clone());
+    });
+    let leaderboard = client.get_leaderboard();
+    assert_eq!(leaderboard.len(), 10);
+    assert!(!leaderboard.iter().any(|(addr, _)| addr == newcomer));
+
+    // Beating it evicts the last entry
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, newcomer.clone());
+    });
+    let leaderboard = client.get_leaderboard();
+    assert_eq!(leaderboard.len(), 10);
+    assert_eq!(leaderboard.get(0), Some((newcomer, 3)));
+    assert!(!leaderboard
+        .iter()
+        .any(|(addr, _)| addr == users.get(9).unwrap()));
+}
+
+#[test]
+fn test_streak_milestone_bonuses() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let mut bonuses = Map::new(&env);
+    bonuses.set(3, 10_0000000);
+    bonuses.set(5, 50_0000000);
+    client.set_streak_bonuses(&bonuses);
+    assert_eq!(client.get_streak_bonuses(), bonuses);
+
+    // Pending winnings after each of six straight wins
+    let expected = [0, 0, 10_0000000, 10_0000000, 60_0000000, 60_0000000];
+    for pending in expected {
+        env.as_contract(&contract_id, || {
+            VirtualTokenContract::_update_stats_win(&env, alice.clone());
+        });
+        assert_eq!(client.get_pending_winnings(&alice), pending);
+    }
+    assert_eq!(client.get_total_supply(), 60_0000000);
+
+    // A loss ends the streak; the next streak earns the milestones again
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+        for _ in 0..3 {
+            VirtualTokenContract::_update_stats_win(&env, alice.clone());
+        }
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 70_0000000);
+}
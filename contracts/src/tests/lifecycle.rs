@@ -18,7 +18,7 @@ fn test_create_round() {
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     env.mock_all_auths();
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create a round
     let start_price: u128 = 1_5000000; // 1.5 XLM in stroops
@@ -79,7 +79,7 @@ fn test_full_round_lifecycle() {
     env.mock_all_auths();
 
     // STEP 1: Initialize contract
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // STEP 2: Users get initial tokens
     client.mint_initial(&alice);
@@ -100,9 +100,9 @@ fn test_full_round_lifecycle() {
     assert_eq!(round.pool_down, 0);
 
     // STEP 4: Users place bets
-    client.place_bet(&alice, &100_0000000, &BetSide::Up);
-    client.place_bet(&bob, &200_0000000, &BetSide::Up);
-    client.place_bet(&charlie, &150_0000000, &BetSide::Down);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &200_0000000, &BetSide::Up, &None);
+    client.place_bet(&charlie, &150_0000000, &BetSide::Down, &None);
 
     // Verify balances deducted
     assert_eq!(client.balance(&alice), 900_0000000);
@@ -180,12 +180,12 @@ fn test_multiple_rounds_lifecycle() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&alice);
 
     // ROUND 1: Alice bets UP and wins
     client.create_round(&1_0000000, &None);
-    client.place_bet(&alice, &100_0000000, &BetSide::Up);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
 
     env.as_contract(&contract_id, || {
         let mut positions = Map::<Address, UserPosition>::new(&env);
@@ -229,7 +229,7 @@ fn test_multiple_rounds_lifecycle() {
 
     // ROUND 2: Alice bets DOWN and wins again
     client.create_round(&2_0000000, &None);
-    client.place_bet(&alice, &100_0000000, &BetSide::Down);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down, &None);
 
     env.as_contract(&contract_id, || {
         let mut positions = Map::<Address, UserPosition>::new(&env);
@@ -271,3 +271,404 @@ fn test_multiple_rounds_lifecycle() {
     assert_eq!(stats.current_streak, 2);
     assert_eq!(stats.best_streak, 2);
 }
+
+#[test]
+fn test_full_precision_round_lifecycle() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    // Precision round: predictions go through the public entry points
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &150_0000000, &2299);
+    client.place_precision_prediction(&charlie, &50_0000000, &2500);
+
+    assert_eq!(client.balance(&alice), 900_0000000);
+    assert_eq!(client.balance(&bob), 850_0000000);
+    assert_eq!(client.balance(&charlie), 950_0000000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2298,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Alice and Bob are both 1 away from 2298 and split the 300 vXLM pot
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 150_0000000);
+    assert_eq!(client.get_pending_winnings(&charlie), 0);
+
+    assert_eq!(client.get_user_stats(&alice).total_wins, 1);
+    assert_eq!(client.get_user_stats(&bob).total_wins, 1);
+    assert_eq!(client.get_user_stats(&charlie).total_losses, 1);
+
+    // Predictions are cleared with the round
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_precision_predictions().len(), 0);
+
+    assert_eq!(client.claim_winnings(&alice), 150_0000000);
+    assert_eq!(client.balance(&alice), 1050_0000000);
+}
+
+#[test]
+fn test_cancel_updown_round_refunds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+
+    client.cancel_round();
+
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 50_0000000);
+
+    // A cancelled round is neither a win nor a loss
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.total_wins, 0);
+    assert_eq!(stats.total_losses, 0);
+
+    client.claim_winnings(&alice);
+    assert_eq!(client.balance(&alice), 1000_0000000);
+}
+
+#[test]
+fn test_cancel_precision_round_refunds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &30_0000000, &2300);
+
+    client.cancel_round();
+
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_precision_predictions().len(), 0);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 30_0000000);
+    assert_eq!(client.get_user_stats(&bob).total_losses, 0);
+}
+
+#[test]
+fn test_cancel_round_without_active_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let result = client.try_cancel_round();
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+}
+
+#[test]
+fn test_get_portfolio() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 1: Alice wins Bob's stake and leaves it unclaimed
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 2: Alice has an open bet
+    client.create_round(&1_5000000, &None);
+    client.place_bet(&alice, &50_0000000, &BetSide::Down, &None);
+
+    let portfolio = client.get_portfolio(&alice);
+    assert_eq!(portfolio.balance, 850_0000000);
+    assert_eq!(portfolio.pending_winnings, 200_0000000);
+    assert_eq!(portfolio.updown_position.len(), 1);
+    assert_eq!(
+        portfolio.updown_position.get(0),
+        Some(UserPosition {
+            amount: 50_0000000,
+            side: BetSide::Down,
+        })
+    );
+    assert!(portfolio.precision_prediction.is_empty());
+    assert_eq!(portfolio.stats.total_wins, 1);
+    assert_eq!(portfolio.stats, client.get_user_stats(&alice));
+}
+
+#[test]
+fn test_auto_compound_rebets_winnings() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_auto_compound(&alice, &true);
+    assert!(client.is_auto_compound(&alice));
+    assert!(!client.is_auto_compound(&bob));
+
+    // Round 1: Alice wins Bob's stake
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+
+    // Round 2: Alice's winnings are re-bet on Up without touching her balance
+    client.create_round(&1_5000000, &None);
+    let position = client.get_user_position(&alice).unwrap();
+    assert_eq!(position.amount, 150_0000000);
+    assert_eq!(position.side, BetSide::Up);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.balance(&alice), 900_0000000);
+    assert_eq!(client.get_pending_claimants().len(), 0);
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.pool_up, 150_0000000);
+    assert_eq!(round.pool_down, 0);
+
+    // Bob did not opt in and has no position
+    assert_eq!(client.get_user_position(&bob), None);
+}
+
+#[test]
+fn test_auto_compound_skips_precision_rounds_and_opt_out() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_auto_compound(&alice, &true);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Down, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // A Precision round leaves the winnings pending
+    client.create_round(&2000, &Some(1));
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+    client.cancel_round();
+
+    // Opting out stops compounding
+    client.set_auto_compound(&alice, &false);
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+}
+
+#[test]
+fn test_auto_compound_leaves_refunds_pending() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_auto_compound(&alice, &true);
+
+    // An unchanged price refunds both stakes
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+
+    // Refunds are not winnings, so nothing is re-bet
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+}
+
+#[test]
+fn test_claim_all_clears_compoundable_winnings() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_auto_compound(&alice, &true);
+
+    // Alice wins while opted in, then claims everything
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.claim_all(&alice), 150_0000000);
+
+    // The next round is refunded
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+
+    // The claimed winnings no longer count as compoundable, so the refund is not re-bet
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+}
+
+#[test]
+fn test_auto_compound_list_is_capped() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let first = Address::generate(&env);
+    client.set_auto_compound(&first, &true);
+    client.set_auto_compound(&first, &true); // Already listed
+    for _ in 1..100 {
+        client.set_auto_compound(&Address::generate(&env), &true);
+    }
+    assert_eq!(client.get_auto_compounders().len(), 100);
+
+    let late = Address::generate(&env);
+    assert_eq!(
+        client.try_set_auto_compound(&late, &true),
+        Err(Ok(ContractError::AutoCompoundFull))
+    );
+
+    // Opting out frees a slot
+    client.set_auto_compound(&first, &false);
+    client.set_auto_compound(&late, &true);
+    assert!(client.is_auto_compound(&late));
+    assert!(!client.get_auto_compounders().contains(&first));
+}
@@ -1,10 +1,21 @@
 //! Test modules for the XLM Price Prediction Market contract.
 
+mod admin;
 mod betting;
+mod custody;
 mod edge_cases;
+mod events;
+mod faucet;
+mod fees;
+mod history;
 mod initialization;
+mod leaderboard;
 mod lifecycle;
 mod mode_tests;
+mod odds;
+mod oracles;
 mod resolution;
 mod security;
+mod storage;
+mod token;
 mod windows;
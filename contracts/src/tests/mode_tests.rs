@@ -2,10 +2,10 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::{BetSide, RoundMode};
+use crate::types::{BetSide, OraclePayload, RoundMode};
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger as _},
-    Address, Env,
+    Address, Env, Vec,
 };
 
 #[test]
@@ -19,7 +19,7 @@ fn test_create_round_default_mode() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create round without specifying mode (should default to UpDown)
     client.create_round(&1_0000000, &None);
@@ -39,7 +39,7 @@ fn test_create_round_updown_mode_explicit() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create round with explicit Up/Down mode (0)
     client.create_round(&1_0000000, &Some(0));
@@ -59,7 +59,7 @@ fn test_create_round_precision_mode() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create round with Precision mode (1)
     client.create_round(&1_0000000, &Some(1));
@@ -68,6 +68,33 @@ fn test_create_round_precision_mode() {
     assert_eq!(round.mode, RoundMode::Precision);
 }
 
+#[test]
+fn test_create_round_precision_weighted_mode() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    // Create round with PrecisionWeighted mode (2)
+    client.create_round(&1_0000000, &Some(2));
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.mode, RoundMode::PrecisionWeighted);
+
+    // Accepts price predictions but not Up/Down bets
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    let result = client.try_place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+}
+
 #[test]
 fn test_create_round_invalid_mode() {
     let env = Env::default();
@@ -79,10 +106,10 @@ fn test_create_round_invalid_mode() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
-    // Try to create round with invalid mode (2)
-    let result = client.try_create_round(&1_0000000, &Some(2));
+    // Try to create round with invalid mode (3)
+    let result = client.try_create_round(&1_0000000, &Some(3));
     assert_eq!(result, Err(Ok(ContractError::InvalidMode)));
 }
 
@@ -98,14 +125,14 @@ fn test_place_bet_on_updown_mode() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Up/Down round
     client.create_round(&1_0000000, &Some(0));
 
     // Place bet should work
-    client.place_bet(&user, &100_0000000, &BetSide::Up);
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
 
     let position = client.get_user_position(&user).unwrap();
     assert_eq!(position.amount, 100_0000000);
@@ -124,14 +151,14 @@ fn test_place_bet_on_precision_mode_fails() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round
     client.create_round(&1_0000000, &Some(1));
 
     // place_bet should fail on Precision mode
-    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up);
+    let result = client.try_place_bet(&user, &100_0000000, &BetSide::Up, &None);
     assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
 }
 
@@ -147,7 +174,7 @@ fn test_place_precision_prediction_on_precision_mode() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round
@@ -177,7 +204,7 @@ fn test_place_precision_prediction_on_updown_mode_fails() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Up/Down round
@@ -200,7 +227,7 @@ fn test_precision_prediction_already_bet() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round
@@ -214,6 +241,56 @@ fn test_precision_prediction_already_bet() {
     assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
 }
 
+#[test]
+fn test_precision_index_with_many_predictions() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&1_0000000, &Some(1));
+
+    let mut users = Vec::new(&env);
+    for i in 0..20u32 {
+        let user = Address::generate(&env);
+        client.mint_initial(&user);
+        client.place_precision_prediction(&user, &10_0000000, &(2000 + i as u128));
+        users.push_back(user);
+    }
+
+    // Every user is still rejected on a second prediction
+    for user in users.iter() {
+        let result = client.try_place_precision_prediction(&user, &10_0000000, &3000);
+        assert_eq!(result, Err(Ok(ContractError::AlreadyBet)));
+    }
+
+    // Withdrawing from the middle keeps later lookups pointing at the right entry
+    client.withdraw_precision_prediction(&users.get(5).unwrap());
+    assert_eq!(
+        client.get_user_precision_prediction(&users.get(5).unwrap()),
+        None
+    );
+    for (i, user) in users.iter().enumerate() {
+        if i == 5 {
+            continue;
+        }
+        let pred = client.get_user_precision_prediction(&user).unwrap();
+        assert_eq!(pred.user, user);
+        assert_eq!(pred.predicted_price, 2000 + i as u128);
+    }
+
+    // The withdrawn user can predict again and is appended at the end
+    client.place_precision_prediction(&users.get(5).unwrap(), &10_0000000, &4242);
+    let predictions = client.get_precision_predictions();
+    assert_eq!(predictions.len(), 20);
+    assert_eq!(predictions.get(19).unwrap().predicted_price, 4242);
+}
+
 #[test]
 fn test_get_precision_predictions() {
     let env = Env::default();
@@ -227,7 +304,7 @@ fn test_get_precision_predictions() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&alice);
     client.mint_initial(&bob);
 
@@ -255,6 +332,138 @@ fn test_get_precision_predictions() {
     assert_eq!(pred1.predicted_price, 2500);
 }
 
+#[test]
+fn test_get_precision_predictions_page() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&1_0000000, &Some(1));
+
+    // Empty list
+    assert_eq!(client.get_precision_prediction_count(), 0);
+    assert!(client.get_precision_predictions_page(&0, &10).is_empty());
+
+    for i in 0..5u32 {
+        let user = Address::generate(&env);
+        client.mint_initial(&user);
+        client.place_precision_prediction(&user, &10_0000000, &(2000 + i as u128));
+    }
+    assert_eq!(client.get_precision_prediction_count(), 5);
+
+    // Full first page
+    let page = client.get_precision_predictions_page(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().predicted_price, 2000);
+    assert_eq!(page.get(1).unwrap().predicted_price, 2001);
+
+    // Partial last page
+    let page = client.get_precision_predictions_page(&4, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().predicted_price, 2004);
+
+    // Out-of-range start and oversized limit
+    assert!(client.get_precision_predictions_page(&9, &2).is_empty());
+    assert_eq!(
+        client.get_precision_predictions_page(&1, &u32::MAX).len(),
+        4
+    );
+}
+
+#[test]
+fn test_get_precision_rank() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&1_0000000, &Some(1));
+
+    // Distances to 2500: 0, 100, 100, 400
+    let mut users = Vec::new(&env);
+    for price in [2500u128, 2400, 2600, 2100] {
+        let user = Address::generate(&env);
+        client.mint_initial(&user);
+        client.place_precision_prediction(&user, &10_0000000, &price);
+        users.push_back(user);
+    }
+
+    assert_eq!(
+        client.get_precision_rank(&users.get(0).unwrap(), &2500),
+        Some((1, 4))
+    );
+    // Tied guesses share a rank and the next one skips past them
+    assert_eq!(
+        client.get_precision_rank(&users.get(1).unwrap(), &2500),
+        Some((2, 4))
+    );
+    assert_eq!(
+        client.get_precision_rank(&users.get(2).unwrap(), &2500),
+        Some((2, 4))
+    );
+    assert_eq!(
+        client.get_precision_rank(&users.get(3).unwrap(), &2500),
+        Some((4, 4))
+    );
+
+    // Ranking follows the reference price
+    assert_eq!(
+        client.get_precision_rank(&users.get(3).unwrap(), &2000),
+        Some((1, 4))
+    );
+
+    assert_eq!(client.get_precision_rank(&outsider, &2500), None);
+}
+
+#[test]
+fn test_precision_round_full() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_max_predictions(), 100);
+
+    // A zero cap would block every prediction
+    let result = client.try_set_max_predictions(&0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoundCap)));
+    assert_eq!(client.get_max_predictions(), 100);
+
+    client.set_max_predictions(&3);
+    client.create_round(&1_0000000, &Some(1));
+    assert_eq!(client.get_prediction_capacity(), (0, 3));
+
+    for i in 0..3u32 {
+        let user = Address::generate(&env);
+        client.mint_initial(&user);
+        client.place_precision_prediction(&user, &10_0000000, &(2000 + i as u128));
+    }
+    assert_eq!(client.get_prediction_capacity(), (3, 3));
+
+    let late = Address::generate(&env);
+    client.mint_initial(&late);
+    let result = client.try_place_precision_prediction(&late, &10_0000000, &2500);
+    assert_eq!(result, Err(Ok(ContractError::RoundFull)));
+    assert_eq!(client.balance(&late), 1000_0000000);
+}
+
 #[test]
 fn test_get_updown_positions() {
     let env = Env::default();
@@ -268,7 +477,7 @@ fn test_get_updown_positions() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&alice);
     client.mint_initial(&bob);
 
@@ -276,8 +485,8 @@ fn test_get_updown_positions() {
     client.create_round(&1_0000000, &Some(0));
 
     // Multiple users place bets
-    client.place_bet(&alice, &100_0000000, &BetSide::Up);
-    client.place_bet(&bob, &150_0000000, &BetSide::Down);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &150_0000000, &BetSide::Down, &None);
 
     // Get all positions
     let positions = client.get_updown_positions();
@@ -294,6 +503,53 @@ fn test_get_updown_positions() {
     assert_eq!(bob_pos.side, BetSide::Down);
 }
 
+#[test]
+fn test_get_updown_positions_list() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert!(client.get_updown_positions_list().is_empty());
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &150_0000000, &BetSide::Down, &None);
+    client.place_bet(&charlie, &20_0000000, &BetSide::Up, &None);
+
+    let list = client.get_updown_positions_list();
+    let positions = client.get_updown_positions();
+    assert_eq!(list.len(), 3);
+
+    // Every pair matches the Map getter, in the Map's key order
+    for ((user, position), (map_user, map_position)) in list.iter().zip(positions.iter()) {
+        assert_eq!(user, map_user);
+        assert_eq!(position, map_position);
+    }
+
+    let expected = [
+        (alice, 100_0000000, BetSide::Up),
+        (bob, 150_0000000, BetSide::Down),
+        (charlie, 20_0000000, BetSide::Up),
+    ];
+    for (user, amount, side) in expected {
+        let (_, position) = list.iter().find(|(u, _)| *u == user).unwrap();
+        assert_eq!(position.amount, amount);
+        assert_eq!(position.side, side);
+    }
+}
+
 #[test]
 fn test_precision_insufficient_balance() {
     let env = Env::default();
@@ -306,7 +562,7 @@ fn test_precision_insufficient_balance() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user); // Has 1000 vXLM
 
     // Create Precision round
@@ -333,7 +589,7 @@ fn test_precision_round_ended() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round (default bet window is 6 ledgers)
@@ -361,7 +617,7 @@ fn test_precision_invalid_amount() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round
@@ -388,7 +644,7 @@ fn test_predict_price_alias() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round
@@ -417,7 +673,7 @@ fn test_predict_price_valid_scales() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Test various valid price scales (4 decimal places)
     let test_cases = [
@@ -460,7 +716,7 @@ fn test_predict_price_invalid_scale() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round
@@ -475,6 +731,45 @@ fn test_predict_price_invalid_scale() {
     assert_eq!(result, Err(Ok(ContractError::InvalidPriceScale)));
 }
 
+#[test]
+fn test_precision_round_rejects_stroop_scaled_prices() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // 0.2297 XLM in stroops is 2_297_000; a huge stroop value is out of 4-decimal range
+    assert_eq!(client.normalize_price(&2_297_000), 2297);
+    assert_eq!(client.normalize_price(&2_297_999), 2297);
+
+    for mode in [1u32, 2] {
+        let result = client.try_create_round(&100_000_000, &Some(mode));
+        assert_eq!(result, Err(Ok(ContractError::InvalidPriceScale)));
+    }
+
+    // Up/Down rounds take stroops, so the same value is accepted there
+    client.create_round(&100_000_000, &None);
+    client.cancel_round();
+
+    // Precision rounds accept 4-decimal prices but not a mis-scaled final price
+    client.create_round(&2297, &Some(1));
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: 100_000_000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::InvalidPriceScale)));
+}
+
 #[test]
 fn test_predict_price_event_emission() {
     let env = Env::default();
@@ -487,7 +782,7 @@ fn test_predict_price_event_emission() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Create Precision round at ledger 0
@@ -502,3 +797,69 @@ fn test_predict_price_event_emission() {
     // Should have events (at least the prediction event)
     assert!(!events.is_empty());
 }
+
+#[test]
+fn test_place_prediction_dispatches_by_mode() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    // Up/Down round takes a side
+    client.create_round(&1_0000000, &None);
+    client.place_prediction(&alice, &100_0000000, &Some(BetSide::Up), &None);
+    let position = client.get_user_position(&alice).unwrap();
+    assert_eq!(position.amount, 100_0000000);
+    assert_eq!(position.side, BetSide::Up);
+
+    // Precision round takes a price
+    client.cancel_round();
+    client.create_round(&1_0000000, &Some(1));
+    client.place_prediction(&alice, &50_0000000, &None, &Some(2297));
+    let prediction = client.get_user_precision_prediction(&alice).unwrap();
+    assert_eq!(prediction.amount, 50_0000000);
+    assert_eq!(prediction.predicted_price, 2297);
+}
+
+#[test]
+fn test_place_prediction_mismatched_arguments() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    let result = client.try_place_prediction(&alice, &100_0000000, &Some(BetSide::Up), &None);
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+
+    // Up/Down round given a price, nothing, or both
+    client.create_round(&1_0000000, &None);
+    let result = client.try_place_prediction(&alice, &100_0000000, &None, &Some(2297));
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+    let result = client.try_place_prediction(&alice, &100_0000000, &None, &None);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+    let result = client.try_place_prediction(&alice, &100_0000000, &Some(BetSide::Up), &Some(2297));
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+
+    // Precision round given a side
+    client.cancel_round();
+    client.create_round(&1_0000000, &Some(1));
+    let result = client.try_place_prediction(&alice, &100_0000000, &Some(BetSide::Down), &None);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+    assert_eq!(client.balance(&alice), 1000_0000000);
+}
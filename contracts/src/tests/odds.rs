@@ -0,0 +1,463 @@
+//! Tests for odds and payout estimate queries.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+#[test]
+fn test_get_odds_balanced_pools() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_odds(), None);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    // Both sides pay 2.0x
+    assert_eq!(client.get_odds(), Some((2_000_000, 2_000_000)));
+}
+
+#[test]
+fn test_get_odds_lopsided_pools() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &300_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    // Up pays 400/300 = 1.333333x, Down pays 400/100 = 4.0x
+    assert_eq!(client.get_odds(), Some((1_333_333, 4_000_000)));
+}
+
+#[test]
+fn test_get_odds_empty_side() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_odds(), Some((0, 0)));
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    assert_eq!(client.get_odds(), Some((1_000_000, 0)));
+}
+
+#[test]
+fn test_get_odds_precision_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&2000, &Some(1));
+    assert_eq!(client.get_odds(), None);
+}
+
+#[test]
+fn test_estimate_payout() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &200_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &150_0000000, &BetSide::Down, &None);
+
+    // Up: 100 + 100 * 150 / 300 = 150
+    assert_eq!(
+        client.estimate_payout(&BetSide::Up, &100_0000000),
+        150_0000000
+    );
+    // Down: 50 + 50 * 200 / 200 = 100
+    assert_eq!(
+        client.estimate_payout(&BetSide::Down, &50_0000000),
+        100_0000000
+    );
+
+    // Read-only: pools are untouched
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.pool_up, 200_0000000);
+    assert_eq!(round.pool_down, 150_0000000);
+}
+
+#[test]
+fn test_estimate_payout_matches_resolution() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_fee_bps(&500);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &200_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &170_0000000, &BetSide::Down, &None);
+
+    let estimate = client.estimate_payout(&BetSide::Up, &100_0000000);
+    client.place_bet(&charlie, &100_0000000, &BetSide::Up, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_pending_winnings(&charlie), estimate);
+}
+
+#[test]
+fn test_estimate_payout_errors() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let result = client.try_estimate_payout(&BetSide::Up, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+
+    client.create_round(&2000, &Some(1));
+    let result = client.try_estimate_payout(&BetSide::Up, &100_0000000);
+    assert_eq!(result, Err(Ok(ContractError::WrongModeForPrediction)));
+
+    let result = client.try_estimate_payout(&BetSide::Up, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+}
+
+#[test]
+fn test_get_tvl_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_tvl(), 0);
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_tvl(), 0);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    assert_eq!(client.get_tvl(), 100_0000000);
+
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+    assert_eq!(client.get_tvl(), 140_0000000);
+
+    // Resolution releases everything
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_tvl(), 0);
+}
+
+#[test]
+fn test_get_tvl_precision() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    assert_eq!(client.get_tvl(), 100_0000000);
+
+    client.place_precision_prediction(&bob, &250_0000000, &2300);
+    assert_eq!(client.get_tvl(), 350_0000000);
+}
+
+#[test]
+fn test_get_projected_payout_updown() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_projected_payout(&alice), 0);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &300_0000000, &BetSide::Down, &None);
+
+    // Alice on the smaller side: 100 + 100 * 300 / 100
+    assert_eq!(client.get_projected_payout(&alice), 400_0000000);
+    // Bob on the larger side: 300 + 300 * 100 / 300
+    assert_eq!(client.get_projected_payout(&bob), 400_0000000);
+    // No position
+    assert_eq!(client.get_projected_payout(&charlie), 0);
+
+    // The projection matches what resolution actually pays
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 400_0000000);
+}
+
+#[test]
+fn test_get_projected_payout_precision() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Closest to the 2000 start price leads
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2010);
+    client.place_precision_prediction(&bob, &50_0000000, &2100);
+
+    assert_eq!(client.get_projected_payout(&alice), 100_0000000);
+    assert_eq!(client.get_projected_payout(&bob), 0);
+}
+
+#[test]
+fn test_place_bet_slippage_protection() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let whale = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&whale);
+    client.mint_initial(&charlie);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    // Betting 100 Down: (200 + 100) / 200 = 1.5x, within a 1.4x floor
+    client.place_bet(&charlie, &100_0000000, &BetSide::Down, &Some(1_400_000));
+    assert_eq!(client.get_odds(), Some((3_000_000, 1_500_000)));
+
+    // A whale piles onto Up: (500 + 200) / 500 = 1.4x, below a 1.5x floor
+    let result = client.try_place_bet(&whale, &400_0000000, &BetSide::Up, &Some(1_500_000));
+    assert_eq!(result, Err(Ok(ContractError::SlippageExceeded)));
+    assert_eq!(client.balance(&whale), 1000_0000000);
+
+    // The exact floor is still accepted
+    client.place_bet(&whale, &400_0000000, &BetSide::Up, &Some(1_400_000));
+}
+
+#[test]
+fn test_get_net_pools() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_net_pools(), None);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.set_fee_bps(&500); // 5%
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &300_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    // Fee is projected on the smaller (Down) pool
+    assert_eq!(
+        client.get_net_pools(),
+        Some((300_0000000, 100_0000000, 5_0000000))
+    );
+}
+
+#[test]
+fn test_get_net_pools_precision_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&2000, &Some(1));
+
+    assert_eq!(client.get_net_pools(), None);
+}
+
+#[test]
+fn test_get_implied_probability() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_implied_probability(), None);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&1_0000000, &None);
+
+    // Empty pools say nothing
+    assert_eq!(client.get_implied_probability(), None);
+
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    assert_eq!(client.get_implied_probability(), Some(5000));
+
+    // 170 of 270 on Up
+    client.place_bet(&charlie, &70_0000000, &BetSide::Up, &None);
+    assert_eq!(client.get_implied_probability(), Some(6296));
+}
+
+#[test]
+fn test_get_implied_probability_precision_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&2000, &Some(1));
+
+    assert_eq!(client.get_implied_probability(), None);
+}
@@ -0,0 +1,516 @@
+//! Tests for multi-oracle price submission and median resolution.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    Address, BytesN, Env, Symbol,
+};
+
+/// Oracle contract stub reporting a fixed XLM price
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    pub fn get_price(_env: Env, asset: Symbol) -> u128 {
+        assert_eq!(asset, symbol_short!("XLM"));
+        1_5000000
+    }
+}
+
+#[test]
+fn test_add_and_remove_oracles() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let o1 = Address::generate(&env);
+    let o2 = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.add_oracle(&o1);
+    client.add_oracle(&o2);
+    client.add_oracle(&o1); // Duplicate is ignored
+
+    let oracles = client.get_oracles();
+    assert_eq!(oracles.len(), 2);
+
+    client.remove_oracle(&o1);
+    let oracles = client.get_oracles();
+    assert_eq!(oracles.len(), 1);
+    assert_eq!(oracles.get(0), Some(o2));
+}
+
+#[test]
+fn test_median_ignores_outlier() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let o1 = Address::generate(&env);
+    let o2 = Address::generate(&env);
+    let o3 = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.add_oracle(&o1);
+    client.add_oracle(&o2);
+    client.add_oracle(&o3);
+    client.set_oracle_quorum(&3);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    // Two honest oracles report a drop, one outlier reports a huge pump
+    client.submit_price(&o1, &9000000);
+    client.submit_price(&o2, &9500000);
+    client.submit_price(&o3, &50_0000000);
+
+    client.finalize_resolution();
+
+    // Median (0.95) is below the start price, so DOWN wins
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&bob), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+}
+
+#[test]
+fn test_finalize_requires_quorum() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let o1 = Address::generate(&env);
+    let o2 = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.add_oracle(&o1);
+    client.add_oracle(&o2);
+    client.set_oracle_quorum(&2);
+
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    let result = client.try_finalize_resolution();
+    assert_eq!(result, Err(Ok(ContractError::InsufficientOraclePrices)));
+
+    client.submit_price(&o1, &1_2000000);
+    let result = client.try_finalize_resolution();
+    assert_eq!(result, Err(Ok(ContractError::InsufficientOraclePrices)));
+
+    client.submit_price(&o2, &1_4000000);
+    client.finalize_resolution();
+    assert_eq!(client.get_active_round(), None);
+}
+
+#[test]
+fn test_submit_price_validation() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let o1 = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.add_oracle(&o1);
+    client.create_round(&1_0000000, &None);
+
+    // Submission window opens at end_ledger
+    let result = client.try_submit_price(&o1, &1_1000000);
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    let result = client.try_submit_price(&stranger, &1_1000000);
+    assert_eq!(result, Err(Ok(ContractError::UnauthorizedOracle)));
+
+    let result = client.try_submit_price(&o1, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidPrice)));
+
+    let result = client.try_set_oracle_quorum(&0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidQuorum)));
+
+    // Submissions close with the resolution window
+    client.set_max_resolve_delay(&5);
+    client.submit_price(&o1, &1_1000000);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 18;
+    });
+    let result = client.try_submit_price(&o1, &1_2000000);
+    assert_eq!(result, Err(Ok(ContractError::ResolutionWindowExpired)));
+    assert_eq!(client.get_price_submissions().get(o1), Some(1_1000000));
+}
+
+#[test]
+fn test_removed_oracle_submission_is_ignored() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let o1 = Address::generate(&env);
+    let o2 = Address::generate(&env);
+    let o3 = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.add_oracle(&o1);
+    client.add_oracle(&o2);
+    client.add_oracle(&o3);
+    client.set_oracle_quorum(&2);
+
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.submit_price(&o1, &9_0000000);
+    client.submit_price(&o2, &1_2000000);
+
+    // o1 no longer counts once removed, so the quorum is one short
+    client.remove_oracle(&o1);
+    let result = client.try_finalize_resolution();
+    assert_eq!(result, Err(Ok(ContractError::InsufficientOraclePrices)));
+
+    // The median is taken over the current oracles only
+    client.submit_price(&o3, &1_4000000);
+    client.finalize_resolution();
+    let resolved = client.get_round_history().get(0).unwrap();
+    assert_eq!(resolved.final_price, 1_3000000);
+}
+
+#[test]
+fn test_resolve_from_oracle_contract() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+    let oracle_contract = env.register(MockPriceOracle, ());
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_oracle_contract(&oracle_contract);
+    assert_eq!(client.get_oracle_contract(), Some(oracle_contract));
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    // Too early to resolve
+    let result = client.try_resolve_from_oracle_contract();
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    // No signature needed - the price comes from the oracle contract
+    env.set_auths(&[]);
+    client.resolve_from_oracle_contract();
+
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&alice), 140_0000000);
+    assert_eq!(
+        client.get_round_history().get(0).unwrap().final_price,
+        1_5000000
+    );
+}
+
+#[test]
+fn test_resolve_from_oracle_contract_not_set() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_from_oracle_contract();
+    assert_eq!(result, Err(Ok(ContractError::OracleNotSet)));
+}
+
+/// Opens a round with opposing Up/Down bets and announces a final price of 1.5 at end_ledger
+fn setup_announced_round(
+    env: &Env,
+    review_window: u32,
+) -> (VirtualTokenContractClient<'_>, Address, Address) {
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_review_window(&review_window);
+    client.set_expiry_grace(&5);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.announce_resolution(&1_5000000);
+
+    (client, alice, bob)
+}
+
+#[test]
+fn test_announce_then_confirm_resolution() {
+    let env = Env::default();
+    let (client, alice, bob) = setup_announced_round(&env, 10);
+
+    let pending = client.get_pending_resolution().unwrap();
+    assert_eq!(pending.price, 1_5000000);
+    assert_eq!(pending.announced_ledger, 12);
+    assert_eq!(pending.confirm_ledger, 22);
+    assert_eq!(pending.deadline_ledger, 27);
+
+    // The announced price can't be replaced while it is pending
+    assert_eq!(
+        client.try_announce_resolution(&2_0000000),
+        Err(Ok(ContractError::ResolutionAlreadyAnnounced))
+    );
+
+    // Nothing is settled during the review window
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 21;
+    });
+    assert_eq!(
+        client.try_confirm_resolution(),
+        Err(Ok(ContractError::ReviewWindowActive))
+    );
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 22;
+    });
+    client.confirm_resolution();
+
+    assert!(client.get_pending_winnings(&alice) > 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_resolution(), None);
+}
+
+#[test]
+fn test_unconfirmed_resolution_is_cancelled_with_refunds() {
+    let env = Env::default();
+    let (client, alice, bob) = setup_announced_round(&env, 10);
+
+    // The oracle may still confirm up to the deadline
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 27;
+    });
+    assert_eq!(
+        client.try_cancel_resolution(),
+        Err(Ok(ContractError::RoundNotEnded))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 28;
+    });
+    assert_eq!(
+        client.try_confirm_resolution(),
+        Err(Ok(ContractError::ResolutionWindowExpired))
+    );
+
+    client.cancel_resolution();
+
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 100_0000000);
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_resolution(), None);
+    assert_eq!(
+        client.try_cancel_resolution(),
+        Err(Ok(ContractError::ResolutionNotAnnounced))
+    );
+}
+
+/// Opens an ended Up/Down round whose oracle signing key is `signer`'s public key
+fn setup_signed_round<'a>(
+    env: &'a Env,
+    signer: &SigningKey,
+) -> (VirtualTokenContractClient<'a>, Address) {
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_oracle_signing_key(&BytesN::from_array(env, &signer.verifying_key().to_bytes()));
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+
+    (client, alice)
+}
+
+/// Signs the active round's resolution message for `price` with `signer`
+fn sign_resolution(
+    env: &Env,
+    client: &VirtualTokenContractClient,
+    signer: &SigningKey,
+    price: u128,
+) -> BytesN<64> {
+    let round = client.get_active_round().unwrap();
+    let message = env.as_contract(&client.address, || {
+        VirtualTokenContract::_resolution_message(env, &round, price)
+    });
+
+    extern crate std;
+    let buf: std::vec::Vec<u8> = message.iter().collect();
+
+    BytesN::from_array(env, &signer.sign(&buf).to_bytes())
+}
+
+#[test]
+fn test_resolve_with_valid_signature() {
+    let env = Env::default();
+    let signer = SigningKey::from_bytes(&[7; 32]);
+    let (client, alice) = setup_signed_round(&env, &signer);
+
+    let signature = sign_resolution(&env, &client, &signer, 1_5000000);
+
+    // A keeper submits it; no oracle auth is needed
+    env.set_auths(&[]);
+    client.resolve_with_signature(&1_5000000, &signature);
+
+    assert_eq!(client.get_active_round(), None);
+    assert!(client.get_pending_winnings(&alice) > 100_0000000);
+}
+
+#[test]
+fn test_resolve_with_tampered_signature() {
+    let env = Env::default();
+    let signer = SigningKey::from_bytes(&[7; 32]);
+    let (client, alice) = setup_signed_round(&env, &signer);
+
+    // Signed for one price but submitted with another; the host verification aborts
+    let signature = sign_resolution(&env, &client, &signer, 1_5000000);
+    assert!(client
+        .try_resolve_with_signature(&2_0000000, &signature)
+        .is_err());
+
+    // Signed by a key other than the oracle's
+    let impostor = SigningKey::from_bytes(&[8; 32]);
+    let signature = sign_resolution(&env, &client, &impostor, 1_5000000);
+    assert!(client
+        .try_resolve_with_signature(&1_5000000, &signature)
+        .is_err());
+
+    // A non-canonical S half is caught before the host is asked to verify
+    let mut malformed = sign_resolution(&env, &client, &signer, 1_5000000).to_array();
+    malformed[32..].fill(0xff);
+    assert_eq!(
+        client.try_resolve_with_signature(&1_5000000, &BytesN::from_array(&env, &malformed)),
+        Err(Ok(ContractError::InvalidSignature))
+    );
+
+    assert!(client.get_active_round().is_some());
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+}
+
+#[test]
+fn test_review_window_blocks_direct_settlement() {
+    let env = Env::default();
+    let (client, alice, _) = setup_announced_round(&env, 10);
+
+    let payload = OraclePayload {
+        price: 2_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    };
+    assert_eq!(
+        client.try_resolve_round(&payload),
+        Err(Ok(ContractError::ReviewWindowActive))
+    );
+    assert_eq!(
+        client.try_resolve_and_pay(&payload),
+        Err(Ok(ContractError::ReviewWindowActive))
+    );
+    assert_eq!(
+        client.try_finalize_resolution(),
+        Err(Ok(ContractError::ReviewWindowActive))
+    );
+
+    // A pending announcement still blocks direct settlement once the window is cleared
+    client.set_review_window(&0);
+    assert_eq!(
+        client.try_resolve_round(&payload),
+        Err(Ok(ContractError::ReviewWindowActive))
+    );
+
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert!(client.get_active_round().is_some());
+}
@@ -2,8 +2,12 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::{BetSide, DataKey, OraclePayload, PrecisionPrediction, Round, UserPosition};
+use crate::types::{
+    BetSide, DataKey, OraclePayload, PrecisionPrediction, Round, RoundingMode, TieBreakMode,
+    UserPosition,
+};
 use soroban_sdk::{
+    contracttype,
     testutils::{Address as _, Ledger as _},
     Address, Env, Map, Vec,
 };
@@ -18,7 +22,7 @@ fn test_resolve_round_price_unchanged() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create a round with start price 1.5 XLM
     let start_price: u128 = 1_5000000;
@@ -112,7 +116,7 @@ fn test_resolve_round_price_went_up() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create a round with start price 1.0 XLM
     let start_price: u128 = 1_0000000;
@@ -217,7 +221,7 @@ fn test_resolve_round_price_went_down() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create a round with start price 2.0 XLM
     let start_price: u128 = 2_0000000;
@@ -288,6 +292,76 @@ fn test_resolve_round_price_went_down() {
     assert_eq!(client.balance(&bob), bob_before); // No change (lost)
 }
 
+#[test]
+fn test_pending_claimants() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    assert!(client.get_pending_claimants().is_empty());
+
+    // Round 1: Alice and Bob win, Charlie loses
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Up, &None);
+    client.place_bet(&charlie, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(
+        client.get_pending_claimants(),
+        Vec::from_array(&env, [alice.clone(), bob.clone()])
+    );
+
+    // Round 2: refunds add Charlie without duplicating Alice
+    client.create_round(&1_5000000, &None);
+    client.place_bet(&alice, &10_0000000, &BetSide::Up, &None);
+    client.place_bet(&charlie, &10_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+    assert_eq!(
+        client.get_pending_claimants(),
+        Vec::from_array(&env, [alice.clone(), bob.clone(), charlie.clone()])
+    );
+
+    assert_eq!(client.get_pending_claimant_count(), 3);
+    assert_eq!(
+        client.get_pending_claimants_page(&1, &5),
+        Vec::from_array(&env, [bob.clone(), charlie.clone()])
+    );
+
+    // Claiming removes the claimant; the last claimant takes over the freed slot
+    client.claim_winnings(&alice);
+    assert_eq!(
+        client.get_pending_claimants(),
+        Vec::from_array(&env, [charlie.clone(), bob.clone()])
+    );
+    client.claim_all(&charlie);
+    assert_eq!(client.get_pending_claimants(), Vec::from_array(&env, [bob]));
+}
+
 #[test]
 fn test_claim_winnings_when_none() {
     let env = Env::default();
@@ -302,6 +376,35 @@ fn test_claim_winnings_when_none() {
     assert_eq!(claimed, 0);
 }
 
+#[test]
+fn test_claim_winnings_overflow() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(alice.clone()), &i128::MAX);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingWinnings(alice.clone()), &1i128);
+    });
+
+    // Returns a clean error instead of panicking, leaving storage untouched
+    let result = client.try_claim_winnings(&alice);
+    assert_eq!(result, Err(Ok(ContractError::Overflow)));
+    assert_eq!(client.balance(&alice), i128::MAX);
+    assert_eq!(client.get_pending_winnings(&alice), 1);
+}
+
 #[test]
 fn test_user_stats_tracking() {
     let env = Env::default();
@@ -313,7 +416,7 @@ fn test_user_stats_tracking() {
     let alice = Address::generate(&env);
 
     env.mock_all_auths();
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Initial stats should be all zeros
     let stats = client.get_user_stats(&alice);
@@ -354,6 +457,57 @@ fn test_user_stats_tracking() {
     assert_eq!(stats.best_streak, 2); // Best remains
 }
 
+#[test]
+fn test_loss_streak_tracking() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    // L, L, W, L, L, L, W
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+    });
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.current_loss_streak, 2);
+    assert_eq!(stats.worst_loss_streak, 2);
+
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, alice.clone());
+    });
+
+    // A win resets the loss streak, mirroring how a loss resets the win streak
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.current_loss_streak, 0);
+    assert_eq!(stats.worst_loss_streak, 2);
+    assert_eq!(stats.current_streak, 1);
+
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+        VirtualTokenContract::_update_stats_loss(&env, alice.clone());
+    });
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.current_streak, 0);
+    assert_eq!(stats.current_loss_streak, 3);
+    assert_eq!(stats.worst_loss_streak, 3);
+
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, alice.clone());
+    });
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.current_loss_streak, 0);
+    assert_eq!(stats.worst_loss_streak, 3);
+    assert_eq!(stats.best_streak, 1);
+    assert_eq!(stats.total_wins, 2);
+    assert_eq!(stats.total_losses, 5);
+}
+
 #[test]
 fn test_resolve_round_without_active_round() {
     let env = Env::default();
@@ -364,7 +518,7 @@ fn test_resolve_round_without_active_round() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Try to resolve without creating a round - should return error
     let result = client.try_resolve_round(&OraclePayload {
@@ -389,7 +543,7 @@ fn test_resolve_precision_closest_guess_wins() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create Precision mode round starting at 2000
     client.create_round(&2000, &Some(1));
@@ -472,7 +626,7 @@ fn test_resolve_precision_tie_splits_pot() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create Precision mode round
     client.create_round(&2000, &Some(1));
@@ -553,7 +707,7 @@ fn test_resolve_precision_exact_match() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     client.create_round(&2000, &Some(1));
 
@@ -610,7 +764,7 @@ fn test_resolve_precision_no_predictions() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Create Precision mode round with no predictions
     client.create_round(&2000, &Some(1));
@@ -628,6 +782,56 @@ fn test_resolve_precision_no_predictions() {
 
     // Round should be cleared
     assert_eq!(client.get_active_round(), None);
+
+    // Nothing paid out, but the resolution is still on record
+    assert_eq!(client.get_pending_winnings(&admin), 0);
+    let history = client.get_round_history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().final_price, 2250);
+}
+
+#[test]
+fn test_resolve_precision_three_way_exact_match() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    let dave = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.mint_initial(&dave);
+
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2200);
+    client.place_precision_prediction(&bob, &200_0000000, &2200);
+    client.place_precision_prediction(&charlie, &100_0000000, &2200);
+    client.place_precision_prediction(&dave, &200_0000000, &2201); // one off
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2200,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // The 600 pot is split evenly among the exact matchers only
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&charlie), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&dave), 0);
+    assert_eq!(client.get_user_stats(&charlie).total_wins, 1);
+    assert_eq!(client.get_user_stats(&dave).total_losses, 1);
 }
 
 #[test]
@@ -640,7 +844,7 @@ fn test_resolve_precision_three_way_tie() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     client.create_round(&2000, &Some(1));
 
@@ -690,11 +894,16 @@ fn test_resolve_precision_three_way_tie() {
         round_id: 0,
     });
 
-    // Total pot is 400, split 3 ways = 133 each (integer division)
+    // Total pot is 400, split 3 ways; the 1-stroop remainder goes to Alice, the earliest guess
     let pot_per_winner = 400_0000000 / 3;
-    assert_eq!(client.get_pending_winnings(&alice), pot_per_winner);
+    assert_eq!(client.get_pending_winnings(&alice), pot_per_winner + 1);
     assert_eq!(client.get_pending_winnings(&bob), pot_per_winner);
     assert_eq!(client.get_pending_winnings(&charlie), pot_per_winner);
+
+    let paid = client.get_pending_winnings(&alice)
+        + client.get_pending_winnings(&bob)
+        + client.get_pending_winnings(&charlie);
+    assert_eq!(paid, 400_0000000);
 }
 
 #[test]
@@ -707,7 +916,7 @@ fn test_resolve_precision_single_prediction() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     client.create_round(&2000, &Some(1));
 
@@ -752,7 +961,7 @@ fn test_resolve_precision_large_differences() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     client.create_round(&100_0000, &Some(1));
 
@@ -797,3 +1006,839 @@ fn test_resolve_precision_large_differences() {
     assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
     assert_eq!(client.get_pending_winnings(&bob), 0);
 }
+
+#[test]
+fn test_user_volume_and_roi() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 1: Alice wins 50 off Bob
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 2: Alice loses 80 to Bob
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &80_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &20_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    let alice_stats = client.get_user_stats(&alice);
+    assert_eq!(alice_stats.total_wagered, 180_0000000);
+    assert_eq!(alice_stats.total_won, 150_0000000);
+    assert_eq!(client.get_user_roi(&alice), -30_0000000);
+
+    let bob_stats = client.get_user_stats(&bob);
+    assert_eq!(bob_stats.total_wagered, 70_0000000);
+    assert_eq!(bob_stats.total_won, 100_0000000);
+    assert_eq!(client.get_user_roi(&bob), 30_0000000);
+}
+
+/// UserStats layout before volume tracking was added
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+struct LegacyUserStats {
+    total_wins: u32,
+    total_losses: u32,
+    current_streak: u32,
+    best_streak: u32,
+}
+
+#[test]
+fn test_legacy_user_stats_migrate_on_read() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::UserStats(alice.clone()),
+            &LegacyUserStats {
+                total_wins: 3,
+                total_losses: 1,
+                current_streak: 2,
+                best_streak: 2,
+            },
+        );
+    });
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.total_wins, 3);
+    assert_eq!(stats.total_losses, 1);
+    assert_eq!(stats.best_streak, 2);
+    assert_eq!(stats.total_wagered, 0);
+    assert_eq!(stats.total_won, 0);
+    assert_eq!(stats.rounds_played, 0);
+
+    // Updating a legacy entry rewrites it in the current layout
+    env.as_contract(&contract_id, || {
+        VirtualTokenContract::_update_stats_win(&env, alice.clone());
+    });
+
+    let stats = client.get_user_stats(&alice);
+    assert_eq!(stats.total_wins, 4);
+    assert_eq!(stats.current_streak, 3);
+    assert_eq!(stats.best_streak, 3);
+}
+
+#[test]
+fn test_rounds_played_counts_wins_losses_and_refunds() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 1: price rises, Alice wins and Bob loses
+    // Round 2: price unchanged, both refunded
+    // Round 3: price falls, Alice loses and Bob wins
+    let prices = [1_5000000u128, 1_5000000, 1_0000000];
+    let mut start_price = 1_0000000u128;
+    for (i, final_price) in prices.iter().enumerate() {
+        let start_ledger = i as u32 * 12;
+        client.create_round(&start_price, &None);
+        client.place_bet(&alice, &10_0000000, &BetSide::Up, &None);
+        client.place_bet(&bob, &10_0000000, &BetSide::Down, &None);
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = start_ledger + 12;
+        });
+        client.resolve_round(&OraclePayload {
+            price: *final_price,
+            timestamp: env.ledger().timestamp(),
+            round_id: start_ledger,
+        });
+        start_price = *final_price;
+    }
+
+    let alice_stats = client.get_user_stats(&alice);
+    assert_eq!(alice_stats.total_wins, 1);
+    assert_eq!(alice_stats.total_losses, 1);
+    assert_eq!(alice_stats.rounds_played, 3);
+
+    let bob_stats = client.get_user_stats(&bob);
+    assert_eq!(bob_stats.total_wins, 1);
+    assert_eq!(bob_stats.total_losses, 1);
+    assert_eq!(bob_stats.rounds_played, 3);
+}
+
+#[test]
+fn test_precision_tolerance_zero_keeps_closest_guess() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_precision_tolerance(&0);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2297);
+    client.place_precision_prediction(&bob, &300_0000000, &2299);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2298,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Tie on distance - even split regardless of stake
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 200_0000000);
+}
+
+#[test]
+fn test_precision_tolerance_splits_by_stake() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_precision_tolerance(&10);
+    assert_eq!(client.get_precision_tolerance(), 10);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2298); // exact
+    client.place_precision_prediction(&bob, &300_0000000, &2308); // 10 off, in band
+    client.place_precision_prediction(&charlie, &200_0000000, &2309); // 11 off
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2298,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Pot of 600 split 1:3 between Alice and Bob
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 450_0000000);
+    assert_eq!(client.get_pending_winnings(&charlie), 0);
+    assert_eq!(client.get_user_stats(&bob).total_wins, 1);
+    assert_eq!(client.get_user_stats(&charlie).total_losses, 1);
+}
+
+#[test]
+fn test_precision_tolerance_falls_back_to_closest() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_precision_tolerance(&5);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&2000, &Some(1));
+    client.place_precision_prediction(&alice, &100_0000000, &2350);
+    client.place_precision_prediction(&bob, &100_0000000, &2400);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2298,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Nobody is within 5, so the closest guess takes the pot
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 0);
+}
+
+#[test]
+fn test_precision_weighted_pays_by_inverse_distance() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+
+    client.create_round(&2000, &Some(2));
+    client.place_precision_prediction(&alice, &100_0000000, &2298); // distance 0, weight 1
+    client.place_precision_prediction(&bob, &100_0000000, &2299); // distance 1, weight 1/2
+    client.place_precision_prediction(&charlie, &100_0000000, &2301); // distance 3, weight 1/4
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2298,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Pot of 300 split 4:2:1; the 1 stroop of dust goes to the exact match
+    let alice_payout = client.get_pending_winnings(&alice);
+    let bob_payout = client.get_pending_winnings(&bob);
+    let charlie_payout = client.get_pending_winnings(&charlie);
+    assert_eq!(alice_payout, 171_4285715);
+    assert_eq!(bob_payout, 85_7142857);
+    assert_eq!(charlie_payout, 42_8571428);
+    assert_eq!(alice_payout + bob_payout + charlie_payout, 300_0000000);
+
+    // Only a payout above the stake counts as a win
+    assert_eq!(client.get_user_stats(&alice).total_wins, 1);
+    assert_eq!(client.get_user_stats(&bob).total_losses, 1);
+    assert_eq!(client.get_user_stats(&charlie).total_losses, 1);
+}
+
+#[test]
+fn test_precision_weighted_single_guess_takes_pot() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    client.create_round(&2000, &Some(2));
+    client.place_precision_prediction(&alice, &100_0000000, &5000);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2298,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // However far off, a lone guess gets its full stake back
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+}
+
+#[test]
+fn test_claim_all_collects_refunds_and_wins() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Round 1: price unchanged, both refunded
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // Round 2: Alice wins Bob's stake
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &50_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+    assert_eq!(client.claim_all(&alice), 200_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.balance(&alice), 1050_0000000);
+
+    // Nothing left to claim
+    assert_eq!(client.claim_all(&alice), 0);
+}
+
+/// Runs an Up/Down round with `bettors` alternating Up/Down and a minimum of three
+/// Returns (pending winnings, total wins) of the first Up bettor after the price rises
+fn resolve_with_min_participants(bettors: u32) -> (i128, u32) {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_min_participants(&3);
+    assert_eq!(client.get_min_participants(), 3);
+
+    client.create_round(&1_0000000, &None);
+    let first = Address::generate(&env);
+    for i in 0..bettors {
+        let user = if i == 0 {
+            first.clone()
+        } else {
+            Address::generate(&env)
+        };
+        client.mint_initial(&user);
+        let side = if i % 2 == 0 {
+            BetSide::Up
+        } else {
+            BetSide::Down
+        };
+        client.place_bet(&user, &100_0000000, &side, &None);
+    }
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    (
+        client.get_pending_winnings(&first),
+        client.get_user_stats(&first).total_wins,
+    )
+}
+
+#[test]
+fn test_round_below_min_participants_is_refunded() {
+    // Two bettors against a minimum of three: stake refunded, no win recorded
+    assert_eq!(resolve_with_min_participants(2), (100_0000000, 0));
+}
+
+#[test]
+fn test_round_at_min_participants_resolves_normally() {
+    // Two Up vs one Down: the first Up bettor wins half the Down stake
+    assert_eq!(resolve_with_min_participants(3), (150_0000000, 1));
+}
+
+/// Runs an Up/Down round with a 1% dead zone and returns Alice's (Up) pending winnings
+fn resolve_with_dead_zone(final_price: u128) -> i128 {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_price_move_threshold_bps(&100);
+    assert_eq!(client.get_price_move_threshold_bps(), 100);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: final_price,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    client.get_pending_winnings(&alice)
+}
+
+#[test]
+fn test_sub_threshold_move_is_refunded() {
+    // A 1% move does not exceed the 1% dead zone
+    assert_eq!(resolve_with_dead_zone(1_0100000), 100_0000000);
+}
+
+#[test]
+fn test_super_threshold_move_pays_out() {
+    // Just past the dead zone, Up wins Bob's stake
+    assert_eq!(resolve_with_dead_zone(1_0100001), 200_0000000);
+}
+
+/// Runs an Up/Down round with a 50% deviation guard and tries to resolve it at `final_price`
+fn resolve_with_deviation_guard(final_price: u128) -> Result<(), ContractError> {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_max_price_deviation_bps(&5000);
+    assert_eq!(client.get_max_price_deviation_bps(), 5000);
+    client.mint_initial(&alice);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: final_price,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    match result {
+        Ok(_) => {
+            assert!(client.get_active_round().is_none());
+            Ok(())
+        }
+        Err(err) => {
+            // A rejected price leaves the round open for a corrected resolution
+            assert!(client.get_active_round().is_some());
+            Err(err.unwrap())
+        }
+    }
+}
+
+#[test]
+fn test_resolution_within_price_deviation() {
+    assert_eq!(resolve_with_deviation_guard(1_5000000), Ok(()));
+    assert_eq!(resolve_with_deviation_guard(5000000), Ok(()));
+}
+
+#[test]
+fn test_resolution_beyond_price_deviation() {
+    assert_eq!(
+        resolve_with_deviation_guard(1_5000001),
+        Err(ContractError::PriceDeviationTooLarge)
+    );
+    assert_eq!(
+        resolve_with_deviation_guard(4999999),
+        Err(ContractError::PriceDeviationTooLarge)
+    );
+}
+
+/// Runs a Precision round with a two-way tie (Alice first, Bob second) and a
+/// farther guess from Charlie, returning their pending winnings
+fn run_two_way_tie(mode: TieBreakMode) -> (i128, i128, i128) {
+    run_two_way_tie_at(mode, 12)
+}
+
+fn run_two_way_tie_at(mode: TieBreakMode, resolve_ledger: u32) -> (i128, i128, i128) {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_tie_break_mode(&mode);
+    assert_eq!(client.get_tie_break_mode(), mode);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    client.create_round(&2000, &Some(1));
+
+    // Alice and Bob are both 100 away from 2200
+    client.place_precision_prediction(&alice, &100_0000000, &2100);
+    client.place_precision_prediction(&bob, &150_0000000, &2300);
+    client.place_precision_prediction(&charlie, &50_0000000, &2500);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = resolve_ledger;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 2200,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    (
+        client.get_pending_winnings(&alice),
+        client.get_pending_winnings(&bob),
+        client.get_pending_winnings(&charlie),
+    )
+}
+
+#[test]
+fn test_precision_tie_break_split() {
+    let (alice, bob, charlie) = run_two_way_tie(TieBreakMode::Split);
+    assert_eq!(alice, 150_0000000);
+    assert_eq!(bob, 150_0000000);
+    assert_eq!(charlie, 0);
+}
+
+#[test]
+fn test_precision_tie_break_earliest() {
+    let (alice, bob, charlie) = run_two_way_tie(TieBreakMode::Earliest);
+    assert_eq!(alice, 300_0000000);
+    assert_eq!(bob, 0);
+    assert_eq!(charlie, 0);
+}
+
+#[test]
+fn test_precision_tie_break_random_is_deterministic() {
+    for ledger in 12..20 {
+        assert_eq!(
+            run_two_way_tie_at(TieBreakMode::Random, ledger),
+            run_two_way_tie_at(TieBreakMode::Random, ledger)
+        );
+    }
+}
+
+#[test]
+fn test_precision_tie_break_random_picks_a_tied_guess() {
+    let mut alice_wins = 0;
+    let mut bob_wins = 0;
+    for ledger in 12..40 {
+        let (alice, bob, charlie) = run_two_way_tie_at(TieBreakMode::Random, ledger);
+
+        // The whole pot goes to exactly one of the tied guesses
+        assert_eq!(charlie, 0);
+        match (alice, bob) {
+            (300_0000000, 0) => alice_wins += 1,
+            (0, 300_0000000) => bob_wins += 1,
+            other => panic!("unexpected payouts {:?}", other),
+        }
+    }
+
+    // Different ledgers draw different winners
+    assert!(alice_wins > 0);
+    assert!(bob_wins > 0);
+}
+
+#[test]
+fn test_resolve_and_pay_credits_balances() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+
+    let payload = |price: u128, round_id: u32| OraclePayload {
+        price,
+        timestamp: env.ledger().timestamp(),
+        round_id,
+    };
+
+    // Same validation as resolve_round
+    let result = client.try_resolve_and_pay(&payload(1_5000000, 0));
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+        li.timestamp = 1000;
+    });
+    let result = client.try_resolve_and_pay(&payload(1_5000000, 12));
+    assert_eq!(result, Err(Ok(ContractError::InvalidOracleRound)));
+    let result = client.try_resolve_and_pay(&OraclePayload {
+        price: 1_5000000,
+        timestamp: 600,
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::StaleOracleData)));
+
+    client.resolve_and_pay(&payload(1_5000000, 0));
+
+    // Winnings land in the balance with nothing left to claim
+    assert_eq!(client.balance(&alice), 1050_0000000);
+    assert_eq!(client.balance(&bob), 950_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert!(client.get_pending_claimants().is_empty());
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 1);
+}
+
+#[test]
+fn test_resolve_and_pay_leaves_earlier_winnings_pending() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Alice leaves 150 vXLM unclaimed from the first round
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+
+    client.create_round(&1_5000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 24;
+    });
+    client.resolve_and_pay(&OraclePayload {
+        price: 2_0000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 12,
+    });
+
+    // Only the second round's payout is credited to the balance
+    assert_eq!(client.balance(&alice), 950_0000000);
+    assert_eq!(client.get_pending_winnings(&alice), 150_0000000);
+    assert_eq!(client.get_pending_claimants().len(), 1);
+}
+
+/// Resolves an Up-winning round with the given Up stakes against one Down stake
+/// under `mode`, returning each Up bettor's payout
+fn payouts_with_rounding(mode: RoundingMode, up_stakes: &[i128], down_stake: i128) -> Vec<i128> {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loser = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_rounding_mode(&mode);
+    assert_eq!(client.get_rounding_mode(), mode);
+
+    client.create_round(&1_0000000, &None);
+    let mut winners = Vec::new(&env);
+    for stake in up_stakes {
+        let user = Address::generate(&env);
+        client.mint_initial(&user);
+        client.place_bet(&user, stake, &BetSide::Up, &None);
+        winners.push_back(user);
+    }
+    client.mint_initial(&loser);
+    client.place_bet(&loser, &down_stake, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    let mut payouts = Vec::new(&env);
+    for user in winners.iter() {
+        payouts.push_back(client.get_pending_winnings(&user));
+    }
+    payouts
+}
+
+#[test]
+fn test_floor_versus_nearest_rounding() {
+    let env = Env::default();
+
+    // Shares of the 3-stroop losing pool are 1.2, 1.05 and 0.75 stroops
+    let floor = payouts_with_rounding(RoundingMode::Floor, &[40, 35, 25], 3);
+    let nearest = payouts_with_rounding(RoundingMode::Nearest, &[40, 35, 25], 3);
+
+    // Floor leaves 1 stroop of dust, which goes to the largest winner
+    assert_eq!(floor, Vec::from_array(&env, [42, 36, 25]));
+    // Nearest rounds 0.75 up and needs no dust
+    assert_eq!(nearest, Vec::from_array(&env, [41, 36, 26]));
+}
+
+#[test]
+fn test_nearest_rounding_never_overpays() {
+    // Three shares of 0.67 stroops would round up to 3 stroops out of a 2-stroop pool
+    let payouts = payouts_with_rounding(RoundingMode::Nearest, &[1, 1, 1], 2);
+
+    assert_eq!(payouts.iter().sum::<i128>(), 5);
+    assert!(payouts.iter().all(|payout| payout == 1 || payout == 2));
+}
+
+#[test]
+fn test_nearest_rounding_keeps_floor_shares() {
+    // Rounding the four half-stroop shares up would leave only 6 of the 80-stake's 8 stroops
+    let payouts = payouts_with_rounding(RoundingMode::Nearest, &[5, 5, 5, 5, 80], 10);
+
+    assert_eq!(payouts.iter().sum::<i128>(), 110);
+    assert_eq!(payouts.get(4).unwrap(), 88);
+    for i in 0..4 {
+        let payout = payouts.get(i).unwrap();
+        assert!(payout == 5 || payout == 6);
+    }
+    assert_eq!(payouts.iter().filter(|payout| *payout == 6).count(), 2);
+}
@@ -2,12 +2,44 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::OraclePayload;
+use crate::types::{BetSide, DataKey, OraclePayload};
 use soroban_sdk::{
+    contract, contractimpl, symbol_short,
     testutils::{Address as _, Ledger as _},
     Address, Env,
 };
 
+/// Stake token stub whose payouts call claim_winnings on the paying contract again
+#[contract]
+pub struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn set_target(env: Env, target: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("target"), &target);
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, _amount: i128) {
+        let target: Option<Address> = env.storage().instance().get(&symbol_short!("target"));
+        if target == Some(from.clone()) {
+            let nested = VirtualTokenContractClient::new(&env, &from).try_claim_winnings(&to);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("rejected"), &nested.is_err());
+        }
+    }
+
+    pub fn balance(_env: Env, _id: Address) -> i128 {
+        1000_0000000
+    }
+
+    pub fn nested_rejected(env: Env) -> Option<bool> {
+        env.storage().instance().get(&symbol_short!("rejected"))
+    }
+}
+
 #[test]
 fn test_resolve_round_stale_timestamp() {
     let env = Env::default();
@@ -18,7 +50,7 @@ fn test_resolve_round_stale_timestamp() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.create_round(&1_0000000, &None);
 
     // Advance ledger time to 1000
@@ -48,7 +80,7 @@ fn test_resolve_round_invalid_round_id() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.create_round(&1_0000000, &None);
 
     env.ledger().with_mut(|li| {
@@ -76,7 +108,7 @@ fn test_resolve_round_valid_payload() {
     let oracle = Address::generate(&env);
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.create_round(&1_0000000, &None);
 
     env.ledger().with_mut(|li| {
@@ -94,3 +126,306 @@ fn test_resolve_round_valid_payload() {
     client.resolve_round(&payload);
     assert_eq!(client.get_active_round(), None);
 }
+
+#[test]
+fn test_resolve_round_inside_max_delay() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_max_resolve_delay(&10);
+    client.create_round(&1_0000000, &None);
+
+    // end_ledger is 12, so ledger 22 is the last one the oracle may resolve
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 22;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_last_resolved_ledger(), Some(22));
+}
+
+#[test]
+fn test_resolve_round_past_max_delay() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_max_resolve_delay(&10);
+    client.mint_initial(&user);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
+
+    // Nobody can force a refund while the oracle may still resolve
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 22;
+    });
+    let result = client.try_refund_expired_round();
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 23;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::ResolutionWindowExpired)));
+
+    // Anyone can now trigger refunds
+    client.refund_expired_round();
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&user), 100_0000000);
+    assert_eq!(client.get_last_resolved_ledger(), None);
+}
+
+#[test]
+fn test_expire_round_after_grace() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_expiry_grace(&100);
+    assert_eq!(client.get_expiry_grace(), 100);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    // Round ends at 12; the deadline itself is still within the grace period
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 112;
+    });
+    let result = client.try_expire_round();
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+    let result = client.try_refund_expired_round();
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    // One ledger later anyone can expire it, without any signature
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 113;
+    });
+    env.set_auths(&[]);
+    client.expire_round();
+
+    assert_eq!(client.get_active_round(), None);
+    assert_eq!(client.get_pending_winnings(&alice), 100_0000000);
+    assert_eq!(client.get_pending_winnings(&bob), 40_0000000);
+    assert_eq!(client.get_user_stats(&alice).total_wins, 0);
+    assert_eq!(client.get_user_stats(&bob).total_losses, 0);
+}
+
+#[test]
+fn test_expire_round_default_grace() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let result = client.try_expire_round();
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+
+    // Roughly a day of grace by default
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12 + 17_280;
+    });
+    let result = client.try_expire_round();
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12 + 17_281;
+    });
+    client.expire_round();
+    assert_eq!(client.get_active_round(), None);
+}
+
+#[test]
+fn test_claim_rejects_reentrant_call() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+    let token_id = env.register(ReentrantToken, ());
+    let token = ReentrantTokenClient::new(&env, &token_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &Some(token_id.clone()));
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // The payout transfer calls claim_winnings again before the outer claim returns
+    token.set_target(&contract_id);
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+
+    // The nested claim failed, so the winnings were paid exactly once
+    assert_eq!(token.nested_rejected(), Some(true));
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().temporary().has(&DataKey::ClaimLock));
+    });
+}
+
+#[test]
+fn test_claim_rejects_while_claim_lock_held() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // The host already aborts contract re-entry, so hold the lock directly to exercise the guard
+    env.as_contract(&contract_id, || {
+        env.storage().temporary().set(&DataKey::ClaimLock, &true);
+    });
+    let result = client.try_claim_winnings(&alice);
+    assert_eq!(result, Err(Ok(ContractError::Reentrancy)));
+    let result = client.try_claim_all(&alice);
+    assert_eq!(result, Err(Ok(ContractError::Reentrancy)));
+    assert_eq!(client.get_pending_winnings(&alice), 200_0000000);
+
+    // Once the in-flight claim finishes, claiming works and releases the lock
+    env.as_contract(&contract_id, || {
+        env.storage().temporary().remove(&DataKey::ClaimLock);
+    });
+    assert_eq!(client.claim_winnings(&alice), 200_0000000);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().temporary().has(&DataKey::ClaimLock));
+    });
+}
+
+#[test]
+fn test_emergency_withdraw_after_timelock() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    // Alice wins a round, leaving unclaimed winnings
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &100_0000000, &BetSide::Down, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    // ...and opens a position in the next one before the admin pauses and walks away
+    client.create_round(&1_5000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.pause();
+
+    assert_eq!(
+        client.try_emergency_withdraw(&alice),
+        Err(Ok(ContractError::EmergencyNotActive))
+    );
+
+    client.set_emergency_delay(&100);
+    client.set_emergency_mode(&true);
+    assert_eq!(client.get_emergency_unlock_ledger(), Some(112));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 111;
+    });
+    assert_eq!(
+        client.try_emergency_withdraw(&alice),
+        Err(Ok(ContractError::EmergencyTimelockActive))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 112;
+    });
+    let expected = client.balance(&alice) + client.get_pending_winnings(&alice) + 100_0000000;
+    assert_eq!(client.emergency_withdraw(&alice), expected);
+    assert_eq!(client.balance(&alice), expected);
+    assert_eq!(client.get_pending_winnings(&alice), 0);
+    assert_eq!(client.get_user_position(&alice), None);
+    assert_eq!(client.get_active_round().unwrap().pool_up, 0);
+
+    // Disabling emergency mode closes the hatch again
+    client.set_emergency_mode(&false);
+    assert_eq!(
+        client.try_emergency_withdraw(&bob),
+        Err(Ok(ContractError::EmergencyNotActive))
+    );
+}
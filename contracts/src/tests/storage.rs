@@ -0,0 +1,106 @@
+//! Tests for persistent storage TTL extension and migration.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::types::{BetSide, DataKey, UserPosition};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger as _},
+    Address, Env, Map,
+};
+
+#[test]
+fn test_entries_survive_many_ledgers() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+
+    client.set_windows(&10, &200_000);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+
+    // Critical entries are bumped well past the default persistent entry lifetime
+    env.as_contract(&contract_id, || {
+        let persistent = env.storage().persistent();
+        assert!(persistent.get_ttl(&DataKey::Balance(alice.clone())) > 200_000);
+        assert!(persistent.get_ttl(&DataKey::Balance(bob.clone())) > 200_000);
+        assert!(persistent.get_ttl(&DataKey::ActiveRound) > 200_000);
+        assert!(persistent.get_ttl(&DataKey::UpDownPositions) > 200_000);
+    });
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 200_000;
+    });
+
+    assert_eq!(client.balance(&alice), 900_0000000);
+    assert_eq!(client.balance(&bob), 1000_0000000);
+    assert_eq!(
+        client.get_user_position(&alice).unwrap().amount,
+        100_0000000
+    );
+    assert_eq!(client.get_active_round().unwrap().pool_up, 100_0000000);
+}
+
+#[test]
+fn test_migrate_positions_merges_legacy_entries() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+
+    // Drifted legacy state: a stale entry for Alice and one Bob is missing from UpDownPositions
+    env.as_contract(&contract_id, || {
+        let mut legacy = Map::<Address, UserPosition>::new(&env);
+        legacy.set(
+            alice.clone(),
+            UserPosition {
+                amount: 5_0000000,
+                side: BetSide::Down,
+            },
+        );
+        legacy.set(
+            bob.clone(),
+            UserPosition {
+                amount: 40_0000000,
+                side: BetSide::Down,
+            },
+        );
+        env.storage().persistent().set(&DataKey::Positions, &legacy);
+    });
+
+    assert_eq!(client.migrate_positions(), 1);
+
+    // Alice's newer entry wins; Bob's legacy entry is carried over
+    let positions = client.get_updown_positions();
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions.get(alice.clone()).unwrap().amount, 100_0000000);
+    assert_eq!(positions.get(alice).unwrap().side, BetSide::Up);
+    assert_eq!(positions.get(bob).unwrap().amount, 40_0000000);
+
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::Positions));
+    });
+
+    // Nothing left to migrate
+    assert_eq!(client.migrate_positions(), 0);
+}
@@ -0,0 +1,450 @@
+//! Tests for vXLM token transfers and burns.
+
+use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
+use crate::errors::ContractError;
+use crate::types::{BetSide, OraclePayload};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal, Vec,
+};
+
+#[test]
+fn test_transfer() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+    client.transfer(&alice, &bob, &250_0000000);
+
+    assert_eq!(client.balance(&alice), 750_0000000);
+    assert_eq!(client.balance(&bob), 250_0000000);
+}
+
+#[test]
+fn test_transfer_to_self_is_noop() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+    client.transfer(&alice, &alice, &250_0000000);
+
+    // Balance is neither duplicated nor lost
+    assert_eq!(client.balance(&alice), 1000_0000000);
+}
+
+#[test]
+fn test_transfer_invalid_amount() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+
+    let result = client.try_transfer(&alice, &bob, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+
+    let result = client.try_transfer(&alice, &bob, &-100);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+}
+
+#[test]
+fn test_transfer_insufficient_balance() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+
+    let result = client.try_transfer(&alice, &bob, &1001_0000000);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+
+    // Nothing moved
+    assert_eq!(client.balance(&alice), 1000_0000000);
+    assert_eq!(client.balance(&bob), 0);
+}
+
+#[test]
+fn test_total_supply_tracks_initial_grants() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert_eq!(client.get_total_supply(), 0);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&charlie);
+    assert_eq!(client.get_total_supply(), 3000_0000000);
+
+    // A repeat mint returns the existing balance without inflating supply
+    client.transfer(&alice, &bob, &100_0000000);
+    assert_eq!(client.mint_initial(&alice), 900_0000000);
+    assert_eq!(client.get_total_supply(), 3000_0000000);
+}
+
+#[test]
+fn test_supply_reconciles_after_full_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_fee_bps(&100);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.mint_initial(&carol);
+
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&carol, &33_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &70_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+
+    assert_eq!(client.get_contract_balance(), 0);
+    assert!(client.get_accumulated_fees() > 0);
+
+    let mut accounted = client.get_contract_balance();
+    for user in [&admin, &oracle, &alice, &bob, &carol] {
+        accounted += client.balance(user) + client.get_pending_winnings(user);
+    }
+    assert_eq!(client.get_total_supply(), accounted);
+}
+
+#[test]
+fn test_mint_respects_max_supply() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_max_supply(), 0);
+    client.set_max_supply(&2100_0000000);
+
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_total_supply(), 2000_0000000);
+
+    // A third full grant would exceed the cap
+    let result = client.try_mint_initial(&charlie);
+    assert_eq!(result, Err(Ok(ContractError::SupplyCapReached)));
+    assert_eq!(client.balance(&charlie), 0);
+
+    // The faucet fits under the remaining headroom once, then hits the cap
+    client.faucet(&alice);
+    assert_eq!(client.get_total_supply(), 2100_0000000);
+    let result = client.try_faucet(&bob);
+    assert_eq!(result, Err(Ok(ContractError::SupplyCapReached)));
+
+    // Lifting the cap allows minting again
+    client.set_max_supply(&0);
+    assert_eq!(client.mint_initial(&charlie), 1000_0000000);
+    assert_eq!(client.get_total_supply(), 3100_0000000);
+}
+
+#[test]
+fn test_admin_mint() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+
+    assert_eq!(client.admin_mint(&alice, &250_0000000), 1250_0000000);
+    assert_eq!(client.balance(&alice), 1250_0000000);
+    assert_eq!(client.get_total_supply(), 1250_0000000);
+
+    let result = client.try_admin_mint(&alice, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+}
+
+#[test]
+fn test_admin_mint_rejects_non_admin() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let intruder = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    env.mock_auths(&[MockAuth {
+        address: &intruder,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "admin_mint",
+            args: (&intruder, 500_0000000i128).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+
+    let result = client.try_admin_mint(&intruder, &500_0000000);
+    assert!(result.is_err());
+    assert_eq!(client.balance(&intruder), 0);
+    assert_eq!(client.get_total_supply(), 0);
+}
+
+#[test]
+fn test_admin_mint_respects_max_supply() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.set_max_supply(&500_0000000);
+
+    client.admin_mint(&alice, &500_0000000);
+    let result = client.try_admin_mint(&alice, &1);
+    assert_eq!(result, Err(Ok(ContractError::SupplyCapReached)));
+    assert_eq!(client.balance(&alice), 500_0000000);
+}
+
+#[test]
+fn test_burn() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+    assert_eq!(client.get_total_supply(), 1000_0000000);
+
+    let new_balance = client.burn(&alice, &400_0000000);
+
+    assert_eq!(new_balance, 600_0000000);
+    assert_eq!(client.balance(&alice), 600_0000000);
+    assert_eq!(client.get_total_supply(), 600_0000000);
+}
+
+#[test]
+fn test_burn_full_balance() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+
+    assert_eq!(client.burn(&alice, &1000_0000000), 0);
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.get_total_supply(), 0);
+}
+
+#[test]
+fn test_burn_invalid_amount() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+
+    let result = client.try_burn(&alice, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+
+    let result = client.try_burn(&alice, &-1);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBetAmount)));
+}
+
+#[test]
+fn test_burn_insufficient_balance() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.mint_initial(&alice);
+
+    let result = client.try_burn(&alice, &1000_0000001);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+    assert_eq!(client.get_total_supply(), 1000_0000000);
+}
+
+#[test]
+fn test_decimals_and_display() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.decimals(), 7);
+
+    assert_eq!(client.to_display(&1000_0000000), (false, 1000, 0));
+    assert_eq!(client.to_display(&1), (false, 0, 1));
+    assert_eq!(client.to_display(&0), (false, 0, 0));
+    assert_eq!(client.to_display(&12_3456789), (false, 12, 3456789));
+    assert_eq!(client.to_display(&-2_5000000), (true, 2, 5000000));
+
+    // Sub-unit negatives keep their sign even though the whole part is 0
+    assert_eq!(client.to_display(&-1), (true, 0, 1));
+    assert_eq!(client.to_display(&-9999999), (true, 0, 9999999));
+    assert_eq!(
+        client.to_display(&i128::MIN),
+        (true, i128::MIN.unsigned_abs() / 10_000_000, 4105728)
+    );
+}
+
+#[test]
+fn test_batch_mint_initial() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.transfer(&alice, &bob, &250_0000000);
+
+    let users = Vec::from_array(&env, [alice.clone(), bob.clone(), charlie.clone()]);
+    let balances = client.batch_mint_initial(&users);
+
+    // Alice and Bob already hold vXLM, so only Charlie is granted
+    assert_eq!(
+        balances,
+        Vec::from_array(&env, [750_0000000, 250_0000000, 1000_0000000])
+    );
+    assert_eq!(client.get_total_supply(), 2000_0000000);
+
+    // Repeating the batch never double-grants
+    client.batch_mint_initial(&users);
+    assert_eq!(client.balance(&charlie), 1000_0000000);
+    assert_eq!(client.get_total_supply(), 2000_0000000);
+}
+
+#[test]
+fn test_batch_mint_initial_rejects_non_admin() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let users = Vec::from_array(&env, [attacker.clone()]);
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "batch_mint_initial",
+                args: (users.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_batch_mint_initial(&users);
+    assert!(result.is_err());
+    assert_eq!(client.balance(&attacker), 0);
+}
+
+#[test]
+fn test_has_minted() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Fresh address
+    assert!(!client.has_minted(&alice));
+
+    client.mint_initial(&alice);
+    assert!(client.has_minted(&alice));
+    assert!(!client.has_minted(&bob));
+
+    // Spending everything leaves a zero entry, which still counts as minted
+    client.burn(&alice, &1000_0000000);
+    assert_eq!(client.balance(&alice), 0);
+    assert!(client.has_minted(&alice));
+    assert_eq!(client.mint_initial(&alice), 0);
+}
@@ -2,7 +2,7 @@
 
 use crate::contract::{VirtualTokenContract, VirtualTokenContractClient};
 use crate::errors::ContractError;
-use crate::types::{BetSide, OraclePayload};
+use crate::types::{BetSide, DataKey, OraclePayload, RoundMode, RoundSummary};
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
     Address, Env,
@@ -20,7 +20,7 @@ fn test_set_windows_admin_only() {
     env.mock_all_auths();
 
     // Initialize contract
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Admin can set windows
     client.set_windows(&10, &20);
@@ -41,7 +41,7 @@ fn test_set_windows_positive_values() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Zero values should fail
     let result = client.try_set_windows(&0, &12);
@@ -65,7 +65,7 @@ fn test_set_windows_bet_must_be_less_than_run() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // bet_ledgers >= run_ledgers should fail
     let result = client.try_set_windows(&12, &12);
@@ -93,7 +93,7 @@ fn test_create_round_uses_configured_windows() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Set custom windows
     client.set_windows(&10, &20);
@@ -125,7 +125,7 @@ fn test_create_round_uses_default_windows() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
 
     // Don't set custom windows, use defaults
     let start_price: u128 = 1_0000000;
@@ -155,7 +155,7 @@ fn test_betting_closes_at_bet_end_ledger() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Set windows: bet closes at ledger 6, round ends at ledger 12
@@ -168,20 +168,20 @@ fn test_betting_closes_at_bet_end_ledger() {
     env.ledger().with_mut(|li| {
         li.sequence_number = 5;
     });
-    client.place_bet(&user, &100_0000000, &BetSide::Up);
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
 
     // Betting should fail at bet_end_ledger
     env.ledger().with_mut(|li| {
         li.sequence_number = 6;
     });
-    let result = client.try_place_bet(&user, &50_0000000, &BetSide::Down);
+    let result = client.try_place_bet(&user, &50_0000000, &BetSide::Down, &None);
     assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
 
     // Betting should fail after bet_end_ledger
     env.ledger().with_mut(|li| {
         li.sequence_number = 10;
     });
-    let result = client.try_place_bet(&user, &50_0000000, &BetSide::Down);
+    let result = client.try_place_bet(&user, &50_0000000, &BetSide::Down, &None);
     assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
 }
 
@@ -201,7 +201,7 @@ fn test_resolution_only_allowed_after_run_ledgers() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Set windows: bet closes at ledger 6, round ends at ledger 12
@@ -211,7 +211,7 @@ fn test_resolution_only_allowed_after_run_ledgers() {
     client.create_round(&1_0000000, &None);
 
     // User places bet
-    client.place_bet(&user, &100_0000000, &BetSide::Up);
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
 
     // Advance past bet window but before run window
     env.ledger().with_mut(|li| {
@@ -258,7 +258,7 @@ fn test_precision_prediction_respects_bet_window() {
 
     env.mock_all_auths();
 
-    client.initialize(&admin, &oracle);
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
     client.mint_initial(&user);
 
     // Set windows
@@ -280,3 +280,506 @@ fn test_precision_prediction_respects_bet_window() {
     let result = client.try_place_precision_prediction(&user, &50_0000000, &2300);
     assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
 }
+
+#[test]
+fn test_get_windows() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // Defaults before and after initialization
+    assert_eq!(client.get_windows(), (6, 12));
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_windows(), (6, 12));
+
+    client.set_windows(&10, &20);
+    assert_eq!(client.get_windows(), (10, 20));
+}
+
+#[test]
+fn test_get_time_remaining() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_time_remaining(), None);
+
+    // Round starts at ledger 0: betting closes at 6, round ends at 12
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_time_remaining(), Some((6, 12)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 4;
+    });
+    assert_eq!(client.get_time_remaining(), Some((2, 8)));
+
+    // Between bet close and round end
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 9;
+    });
+    assert_eq!(client.get_time_remaining(), Some((0, 3)));
+
+    // After round end both saturate at 0
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    assert_eq!(client.get_time_remaining(), Some((0, 0)));
+}
+
+#[test]
+fn test_get_round_mode() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    assert_eq!(client.get_round_mode(), None);
+
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_round_mode(), Some(0));
+
+    client.cancel_round();
+    client.create_round(&1_0000000, &Some(1));
+    assert_eq!(client.get_round_mode(), Some(1));
+}
+
+#[test]
+fn test_can_resolve() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // No active round
+    assert!(!client.can_resolve());
+
+    // Before the round ends at ledger 12
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 11;
+    });
+    assert!(!client.can_resolve());
+
+    // From the end ledger onwards
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    assert!(client.can_resolve());
+
+    // Not once the resolution window has expired
+    client.set_max_resolve_delay(&5);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 18;
+    });
+    assert!(!client.can_resolve());
+}
+
+#[test]
+fn test_is_betting_open() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // No active round
+    assert!(!client.is_betting_open());
+
+    // Before the bet window closes at ledger 6
+    client.create_round(&1_0000000, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 5;
+    });
+    assert!(client.is_betting_open());
+
+    // At and after bet_end_ledger
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 6;
+    });
+    assert!(!client.is_betting_open());
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 7;
+    });
+    assert!(!client.is_betting_open());
+}
+
+#[test]
+fn test_get_round_phase() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // No active round
+    assert_eq!(client.get_round_phase(), 0);
+
+    // Betting until ledger 6
+    client.create_round(&1_0000000, &None);
+    assert_eq!(client.get_round_phase(), 1);
+
+    // Locked until the round ends at ledger 12
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 6;
+    });
+    assert_eq!(client.get_round_phase(), 2);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 11;
+    });
+    assert_eq!(client.get_round_phase(), 2);
+
+    // Resolvable from the end ledger, and back to none once resolved
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    assert_eq!(client.get_round_phase(), 3);
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_round_phase(), 0);
+}
+
+#[test]
+fn test_get_round_summary() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    assert_eq!(client.get_round_summary(), None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 100;
+    });
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.place_bet(&bob, &40_0000000, &BetSide::Down, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 103;
+    });
+    assert_eq!(
+        client.get_round_summary(),
+        Some(RoundSummary {
+            price_start: 1_0000000,
+            pool_up: 100_0000000,
+            pool_down: 40_0000000,
+            mode: RoundMode::UpDown,
+            start_ledger: 100,
+            bet_end_ledger: 106,
+            end_ledger: 112,
+            current_ledger: 103,
+            betting_open: true,
+        })
+    );
+
+    // Betting closes at bet_end_ledger
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 106;
+    });
+    let summary = client.get_round_summary().unwrap();
+    assert_eq!(summary.current_ledger, 106);
+    assert!(!summary.betting_open);
+}
+
+#[test]
+fn test_set_windows_rejected_mid_round() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // No active round
+    client.set_windows(&10, &20);
+
+    // Active round keeps its captured windows
+    client.create_round(&1_0000000, &None);
+    let result = client.try_set_windows(&5, &8);
+    assert_eq!(result, Err(Ok(ContractError::RoundInProgress)));
+    assert_eq!(client.get_windows(), (10, 20));
+
+    // Allowed again once the round is resolved, applying to the next round
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    client.set_windows(&5, &8);
+
+    client.create_round(&1_0000000, &None);
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 25);
+    assert_eq!(round.end_ledger, 28);
+}
+
+#[test]
+fn test_extend_active_round_before_bet_close() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&user);
+    client.create_round(&1_0000000, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 5;
+    });
+    client.extend_active_round(&4, &10);
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 10);
+    assert_eq!(round.end_ledger, 22);
+
+    // Betting stays open past the original bet window
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 8;
+    });
+    client.place_bet(&user, &100_0000000, &BetSide::Up, &None);
+
+    // Resolution waits for the new end ledger
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+}
+
+#[test]
+fn test_extend_active_round_rejections() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    let result = client.try_extend_active_round(&1, &1);
+    assert_eq!(result, Err(Ok(ContractError::NoActiveRound)));
+
+    client.create_round(&1_0000000, &None);
+
+    // Bet window may not be pushed past the end of the round
+    let result = client.try_extend_active_round(&10, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+
+    // A locked or ended round cannot be extended
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    let result = client.try_extend_active_round(&1, &1);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 6);
+    assert_eq!(round.end_ledger, 12);
+}
+
+#[test]
+fn test_lock_betting_closes_bets_early() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.mint_initial(&bob);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 3;
+    });
+    client.lock_betting();
+
+    let round = client.get_active_round().unwrap();
+    assert_eq!(round.bet_end_ledger, 3);
+    assert_eq!(round.end_ledger, 12);
+    assert!(!client.is_betting_open());
+
+    let result = client.try_place_bet(&bob, &50_0000000, &BetSide::Down, &None);
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+
+    // Locking twice is rejected
+    let result = client.try_lock_betting();
+    assert_eq!(result, Err(Ok(ContractError::RoundEnded)));
+}
+
+#[test]
+fn test_lock_betting_keeps_resolution_timing() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+    client.mint_initial(&alice);
+    client.create_round(&1_0000000, &None);
+    client.place_bet(&alice, &100_0000000, &BetSide::Up, &None);
+    client.lock_betting();
+
+    // Still too early to resolve
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 11;
+    });
+    let result = client.try_resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(result, Err(Ok(ContractError::RoundNotEnded)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12;
+    });
+    client.resolve_round(&OraclePayload {
+        price: 1_5000000,
+        timestamp: env.ledger().timestamp(),
+        round_id: 0,
+    });
+    assert_eq!(client.get_active_round(), None);
+}
+
+#[test]
+fn test_create_round_near_ledger_overflow() {
+    let env = Env::default();
+    // Keep entry lifetimes short so they fit below u32::MAX
+    env.ledger().with_mut(|li| {
+        li.sequence_number = u32::MAX - 11;
+        li.min_persistent_entry_ttl = 1;
+        li.min_temp_entry_ttl = 1;
+        li.max_entry_ttl = 2;
+    });
+
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // The bet window still fits but the run window does not
+    let result = client.try_create_round(&1_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::Overflow)));
+    assert_eq!(client.get_active_round(), None);
+}
+
+#[test]
+fn test_create_round_rejects_inconsistent_windows() {
+    let env = Env::default();
+    let contract_id = env.register(VirtualTokenContract, ());
+    let client = VirtualTokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &oracle, &1000_0000000, &None);
+
+    // Windows written around set_windows' validation
+    let set_windows = |bet: u32, run: u32| {
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::BetWindowLedgers, &bet);
+            env.storage()
+                .persistent()
+                .set(&DataKey::RunWindowLedgers, &run);
+        });
+    };
+
+    set_windows(12, 6);
+    let result = client.try_create_round(&1_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+
+    set_windows(0, 12);
+    let result = client.try_create_round(&1_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+
+    set_windows(6, 6);
+    let result = client.try_create_round(&1_0000000, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidDuration)));
+    assert_eq!(client.get_active_round(), None);
+}
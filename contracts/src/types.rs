@@ -1,14 +1,43 @@
 //! Type definitions for the XLM Price Prediction Market.
 
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Vec};
 
 /// Round mode for prediction type
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 #[repr(u32)]
 pub enum RoundMode {
-    UpDown = 0,    // Simple up/down predictions
-    Precision = 1, // Exact price predictions (Legends mode)
+    UpDown = 0,            // Simple up/down predictions
+    Precision = 1,         // Exact price predictions (Legends mode)
+    PrecisionWeighted = 2, // Exact price predictions paid by inverse distance
+}
+
+/// How tied Precision guesses share the pot
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum TieBreakMode {
+    Split = 0,    // Tied guesses split the pot evenly; the earliest takes the rounding dust
+    Earliest = 1, // The tied guess submitted first takes the whole pot
+    Random = 2,   // One tied guess, drawn by stake from ledger data, takes the whole pot
+}
+
+/// How proportional Up/Down payout shares are rounded to whole stroops
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum RoundingMode {
+    Floor = 0,   // Shares are rounded down; the leftover dust goes to the largest winner
+    Nearest = 1, // Leftover stroops go to the shares with the largest remainders
+}
+
+/// What happens to an Up/Down pot when the price is unchanged
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum DrawPolicy {
+    Refund = 0,     // Every bettor gets their stake back
+    HouseKeeps = 1, // Both pools go to the treasury
 }
 
 /// Storage keys for contract data
@@ -22,10 +51,70 @@ pub enum DataKey {
     Positions,
     UpDownPositions,    // Map<Address, i128> for Up/Down mode
     PrecisionPositions, // Vec<PrecisionPrediction> for Precision mode
+    PrecisionIndex,     // Map<Address, u32> of each user's index into PrecisionPositions
     PendingWinnings(Address),
     UserStats(Address),
-    BetWindowLedgers, // Bet window duration in ledgers
-    RunWindowLedgers, // Run window duration in ledgers
+    BetWindowLedgers,       // Bet window duration in ledgers
+    RunWindowLedgers,       // Run window duration in ledgers
+    TotalSupply,            // Total vXLM in circulation
+    FeeBps,                 // House fee on the losing pool in basis points
+    Treasury,               // Address credited with house fees
+    AccumulatedFees,        // Total house fees collected
+    Paused,                 // Circuit breaker for new bets
+    PendingAdmin,           // Proposed admin awaiting acceptance
+    Oracles,                // Vec<Address> of oracles allowed to submit prices
+    OracleQuorum,           // Minimum price submissions needed to finalize
+    PriceSubmissions,       // Map<Address, u128> of submitted prices for the active round
+    MaxResolveDelay,        // Ledgers after end_ledger the oracle may still resolve
+    LastResolvedLedger,     // Ledger at which the last round was resolved
+    Leaderboard,            // Vec<(Address, u32)> of top users by best streak
+    PrecisionTolerance,     // Price band within which precision guesses win
+    InitialGrant,           // vXLM handed out by mint_initial
+    FaucetCooldown,         // Ledgers a user must wait between faucet claims
+    FaucetAmount,           // vXLM handed out per faucet claim
+    LastFaucet(Address),    // Ledger of the user's last faucet claim
+    RoundHistory,           // Vec<ResolvedRound> of the most recently resolved rounds
+    MinParticipants,        // Positions a round needs to resolve; fewer are refunded
+    PriceMoveThresholdBps,  // Up/Down moves within this share of price_start count as unchanged
+    StakeToken,             // Token contract holding real stakes in custody (virtual vXLM if unset)
+    OracleContract,         // Oracle contract rounds can pull their final price from
+    ResolverRewardBps,      // Share of the losing pool paid to the oracle that resolves
+    ExpiryGrace,            // Ledgers after end_ledger before anyone may expire the round
+    MaxPredictions,         // Precision predictions a single round accepts
+    MaxParticipants,        // Up/Down bettors a single round accepts
+    TieBreakMode,           // How tied Precision guesses share the pot
+    MaxSupply,              // Cap on TotalSupply (0 means unlimited)
+    MaxPriceDeviationBps,   // Largest final price move from price_start accepted at resolution
+    TestMode,               // Enables testnet-only helpers such as reset_my_stats
+    DrawPolicy,             // What happens to an Up/Down pot when the price is unchanged
+    ClaimLock,              // Temporary-storage flag set while a claim pays out, blocking reentry
+    ClaimExpiry,            // Ledgers pending winnings may sit unclaimed before they can be swept
+    LastCredited(Address),  // Ledger at which the user's pending winnings were last credited
+    BetHistory(Address),    // Vec<HistoryEntry> of the user's most recently resolved bets
+    BetCooldown,            // Ledgers a user must wait between bets (0 = disabled)
+    LastBetLedger(Address), // Ledger of the user's last bet or prediction
+}
+
+/// Further storage keys; #[contracttype] enums are capped at 50 variants and DataKey is full
+#[contracttype]
+#[derive(Clone)]
+pub enum ExtDataKey {
+    LastBetSide(Address),  // Side of the user's most recent Up/Down bet
+    AutoCompound(Address), // User opted in to re-betting pending winnings each Up/Down round
+    ReviewWindow,          // Ledgers an announced resolution stays under review before confirmation
+    PendingResolution,     // Price announced for the active round, awaiting confirmation
+    EmergencyMode,         // Ledger from which emergency withdrawals are allowed
+    EmergencyDelay,        // Timelock in ledgers between enabling emergency mode and withdrawals
+    StreakBonuses,         // Map of win streak milestone -> bonus credited on reaching it
+    RoundWinners(u32),     // Participants of the nth resolved round with bets -> whether they won
+    RoundWinnersCount,     // Number of RoundWinners entries ever written
+    RoundingMode,          // How proportional Up/Down payout shares are rounded
+    OracleSigningKey,      // Ed25519 public key the oracle signs resolution prices with
+    AutoCompounders,       // Users opted in to auto-compounding (capped)
+    Compoundable(Address), // Pending Up/Down winnings earned while opted in to auto-compound
+    Claimant(u32),         // User in the nth slot of the pending claimants set
+    ClaimantSlot(Address), // Slot the user occupies in the pending claimants set
+    ClaimantCount,         // Number of users with unclaimed winnings
 }
 
 /// Represents which side a user bet on
@@ -50,6 +139,11 @@ pub struct UserStats {
     pub total_losses: u32,
     pub current_streak: u32,
     pub best_streak: u32,
+    pub total_wagered: i128, // Total vXLM staked in rounds that were won or lost
+    pub total_won: i128,     // Total vXLM paid out from won rounds (stake included)
+    pub current_loss_streak: u32,
+    pub worst_loss_streak: u32,
+    pub rounds_played: u32, // Resolved rounds the user had a position in, refunds included
 }
 
 /// Precision prediction entry (user address + predicted price)
@@ -61,6 +155,18 @@ pub struct PrecisionPrediction {
     pub amount: i128,          // Bet amount
 }
 
+/// Everything about a user in one call: balance, claims, open positions and stats
+/// Contract types cannot hold optional structs, so positions are empty or single-entry Vecs
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Portfolio {
+    pub balance: i128,                                  // Stake token or vXLM balance
+    pub pending_winnings: i128,                         // Unclaimed winnings
+    pub updown_position: Vec<UserPosition>,             // Up/Down bet in the active round
+    pub precision_prediction: Vec<PrecisionPrediction>, // Precision guess in the active round
+    pub stats: UserStats,                               // Lifetime stats
+}
+
 /// Oracle payload including price, timestamp and target round
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -79,5 +185,54 @@ pub struct Round {
     pub end_ledger: u32,     // Ledger when round ends (~5s per ledger)
     pub pool_up: i128,       // Total vXLM bet on UP
     pub pool_down: i128,     // Total vXLM bet on DOWN
-    pub mode: RoundMode,     // Round mode: UpDown (0) or Precision (1)
+    pub mode: RoundMode,     // Round mode: UpDown (0), Precision (1) or PrecisionWeighted (2)
+}
+
+/// Final price announced by the oracle for the active round, awaiting confirmation
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingResolution {
+    pub price: u128,           // Announced final price
+    pub announced_ledger: u32, // Ledger at which the price was announced
+    pub confirm_ledger: u32,   // First ledger at which the oracle may confirm
+    pub deadline_ledger: u32,  // Last ledger at which the oracle may confirm
+}
+
+/// Record of a resolved round kept in the round history
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedRound {
+    pub price_start: u128,    // Starting XLM price in stroops
+    pub final_price: u128,    // Price the round was resolved at
+    pub pool_up: i128,        // Total vXLM bet on UP
+    pub pool_down: i128,      // Total vXLM bet on DOWN
+    pub mode: RoundMode,      // Round mode the round was played in
+    pub resolved_ledger: u32, // Ledger at which the round was resolved
+    pub outcome: u32,         // 0 = down, 1 = up, 2 = unchanged, 3 = precision
+}
+
+/// One of a user's resolved bets, kept in their bet history
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub round_start_price: u128, // Starting price of the round
+    pub side_or_predicted: u128, // Up/Down: 1 = Up, 0 = Down; Precision: predicted price
+    pub amount: i128,            // Stake placed
+    pub payout: i128,            // Amount credited at resolution (0 for a loss)
+    pub resolved_ledger: u32,    // Ledger at which the round was resolved
+}
+
+/// Snapshot of the active round for dashboards
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundSummary {
+    pub price_start: u128,   // Starting XLM price in stroops
+    pub pool_up: i128,       // Total vXLM bet on UP
+    pub pool_down: i128,     // Total vXLM bet on DOWN
+    pub mode: RoundMode,     // Round mode
+    pub start_ledger: u32,   // Ledger when round was created
+    pub bet_end_ledger: u32, // Ledger when betting closes
+    pub end_ledger: u32,     // Ledger when round ends
+    pub current_ledger: u32, // Ledger the summary was taken at
+    pub betting_open: bool,  // Whether bets are still accepted
 }